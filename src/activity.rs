@@ -0,0 +1,232 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use crate::config::ConfigManager;
+use crate::project::Project;
+
+/// What was done with a project
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityKind {
+    Switch,
+    Run,
+    Start,
+}
+
+/// One use of a project, as stored in ~/.projectctl/activity.jsonl
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Activity {
+    /// Full name, including `:package` or `@branch`
+    pub project: String,
+    pub kind: ActivityKind,
+    pub time: DateTime<Utc>,
+}
+
+/// Events kept when the activity log is compacted
+const MAX_EVENTS: usize = 2000;
+
+/// Size past which the activity log is compacted (roughly twice `MAX_EVENTS`)
+const COMPACT_BYTES: u64 = 400 * 1024;
+
+/// Append a use of `project` to the activity log, dropping the oldest
+/// events once it grows past `COMPACT_BYTES`
+pub fn record(config: &ConfigManager, project: &str, kind: ActivityKind) -> Result<()> {
+    config.ensure_dirs()?;
+    let event = Activity {
+        project: project.to_string(),
+        kind,
+        time: Utc::now(),
+    };
+    let line = serde_json::to_string(&event).context("Failed to serialize activity")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(config.activity_path())
+        .context("Failed to open activity.jsonl")?;
+    writeln!(file, "{}", line).context("Failed to write activity.jsonl")?;
+
+    if file.metadata().is_ok_and(|m| m.len() > COMPACT_BYTES) {
+        compact(config)?;
+    }
+    Ok(())
+}
+
+/// Rewrite the activity log with only its newest `MAX_EVENTS` events.
+/// Older visits barely change frecency scores.
+fn compact(config: &ConfigManager) -> Result<()> {
+    let events = load(config)?;
+    let keep = &events[events.len().saturating_sub(MAX_EVENTS)..];
+    let mut content = String::new();
+    for event in keep {
+        content.push_str(&serde_json::to_string(event).context("Failed to serialize activity")?);
+        content.push('\n');
+    }
+    let path = config.activity_path();
+    let tmp = path.with_extension("jsonl.tmp");
+    fs::write(&tmp, content).context("Failed to write activity.jsonl")?;
+    fs::rename(&tmp, &path).context("Failed to replace activity.jsonl")?;
+    Ok(())
+}
+
+/// Load the activity log, oldest first. Unreadable lines are skipped.
+pub fn load(config: &ConfigManager) -> Result<Vec<Activity>> {
+    let path = config.activity_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).context("Failed to read activity.jsonl")?;
+    Ok(content
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+/// The project switched to before the current one, for `switch -`
+pub fn previous_switch(events: &[Activity]) -> Option<&str> {
+    let mut switches = events
+        .iter()
+        .rev()
+        .filter(|e| e.kind == ActivityKind::Switch)
+        .map(|e| e.project.as_str());
+    let current = switches.next()?;
+    switches.find(|p| *p != current)
+}
+
+/// Frecency of a registered project: how often and how recently it was used
+#[derive(Debug, Clone)]
+pub struct Frecency {
+    pub project: String,
+    pub score: f64,
+    pub visits: usize,
+    /// Visits per age bucket of `AGE_BUCKETS`
+    pub buckets: [usize; 4],
+    pub last: Option<DateTime<Utc>>,
+}
+
+impl Frecency {
+    /// How the score adds up, e.g. `2×4 (last hour) + 5×0.5 (last week)`
+    pub fn breakdown(&self) -> String {
+        let parts: Vec<String> = self
+            .buckets
+            .iter()
+            .zip(AGE_BUCKETS)
+            .filter(|(count, _)| **count > 0)
+            .map(|(count, (label, weight))| format!("{}×{} ({})", count, weight, label))
+            .collect();
+        if parts.is_empty() {
+            "no visits".to_string()
+        } else {
+            parts.join(" + ")
+        }
+    }
+}
+
+/// Weight of a visit by its age, as in zoxide
+pub const AGE_BUCKETS: [(&str, f64); 4] = [
+    ("last hour", 4.0),
+    ("last day", 2.0),
+    ("last week", 0.5),
+    ("older", 0.25),
+];
+
+fn age_bucket(age: Duration) -> usize {
+    if age < Duration::hours(1) {
+        0
+    } else if age < Duration::days(1) {
+        1
+    } else if age < Duration::weeks(1) {
+        2
+    } else {
+        3
+    }
+}
+
+/// Rank registered projects by frecency, highest first. Uses of packages and
+/// worktrees count for their project. Projects that predate the activity
+/// log count their `last_used` time as one visit.
+pub fn frecency(projects: &[Project], events: &[Activity]) -> Vec<Frecency> {
+    let now = Utc::now();
+    let mut ranking: Vec<Frecency> = projects
+        .iter()
+        .map(|project| {
+            let mut times: Vec<DateTime<Utc>> = events
+                .iter()
+                .filter(|e| e.project.split([':', '@']).next() == Some(project.name.as_str()))
+                .map(|e| e.time)
+                .collect();
+            if times.is_empty() {
+                times.extend(project.last_used_time());
+            }
+            let mut buckets = [0; 4];
+            for time in &times {
+                buckets[age_bucket(now - *time)] += 1;
+            }
+            Frecency {
+                project: project.name.clone(),
+                score: buckets
+                    .iter()
+                    .zip(AGE_BUCKETS)
+                    .map(|(count, (_, weight))| *count as f64 * weight)
+                    .sum(),
+                visits: times.len(),
+                buckets,
+                last: times.iter().max().copied(),
+            }
+        })
+        .collect();
+    ranking.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.last.cmp(&a.last)));
+    ranking
+}
+
+/// How a project name matched a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    Exact,
+    Prefix,
+    Substring,
+}
+
+impl MatchKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MatchKind::Exact => "exact",
+            MatchKind::Prefix => "prefix",
+            MatchKind::Substring => "substring",
+        }
+    }
+}
+
+/// Projects whose name matches `query`, best first: an exact match wins,
+/// other matches are ordered by frecency, then prefix before substring,
+/// then by the most recent use
+pub fn rank_matches<'a>(ranking: &'a [Frecency], query: &str) -> Vec<(MatchKind, &'a Frecency)> {
+    let query = query.to_lowercase();
+    let mut matches: Vec<(MatchKind, &Frecency)> = ranking
+        .iter()
+        .filter_map(|f| {
+            let name = f.project.to_lowercase();
+            let kind = if name == query {
+                MatchKind::Exact
+            } else if name.starts_with(&query) {
+                MatchKind::Prefix
+            } else if name.contains(&query) {
+                MatchKind::Substring
+            } else {
+                return None;
+            };
+            Some((kind, f))
+        })
+        .collect();
+    matches.sort_by(|(ka, a), (kb, b)| {
+        (*ka != MatchKind::Exact)
+            .cmp(&(*kb != MatchKind::Exact))
+            .then(b.score.total_cmp(&a.score))
+            .then(ka.cmp(kb))
+            .then(b.last.cmp(&a.last))
+            .then(a.project.cmp(&b.project))
+    });
+    matches
+}
@@ -0,0 +1,142 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::ConfigManager;
+use crate::git::GitInfo;
+use crate::probe::ProbeCache;
+use crate::project::Project;
+use crate::services::ServiceStatus;
+
+/// Cached service and git state older than this is shown as stale
+pub const STALE_AFTER_MINUTES: i64 = 10;
+
+/// Last-known status of one project, with the time each part was probed
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CacheEntry {
+    #[serde(default)]
+    pub services: Vec<ServiceStatus>,
+    #[serde(default)]
+    pub services_updated: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub git: Option<GitInfo>,
+    #[serde(default)]
+    pub git_updated: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub outdated: Option<usize>,
+    #[serde(default)]
+    pub outdated_updated: Option<DateTime<Utc>>,
+}
+
+/// On-disk cache of project status under ~/.projectctl/cache/
+pub struct StatusCache {
+    path: PathBuf,
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl StatusCache {
+    pub fn load(config: &ConfigManager) -> Result<Self> {
+        let path = config.cache_dir().join("status.json");
+        let entries = if path.exists() {
+            let content = fs::read_to_string(&path).context("Failed to read status cache")?;
+            // A corrupt or outdated cache is simply rebuilt
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).context("Failed to create cache directory")?;
+        }
+        let content =
+            serde_json::to_string_pretty(&self.entries).context("Failed to serialize cache")?;
+        fs::write(&self.path, content).context("Failed to write status cache")?;
+        Ok(())
+    }
+
+    pub fn entry(&self, name: &str) -> Option<&CacheEntry> {
+        self.entries.get(name)
+    }
+
+    /// Fill `probes` with the last-known state of each project.
+    /// Returns the projects that have no cached state yet (including git
+    /// state when `git` is set).
+    pub fn seed(&self, probes: &ProbeCache, projects: &[Project], git: bool) -> Vec<Project> {
+        let mut missing = Vec::new();
+        for project in projects {
+            match self.entries.get(&project.name) {
+                Some(entry)
+                    if entry.services_updated.is_some()
+                        && (!git || entry.git_updated.is_some()) =>
+                {
+                    probes.insert_compose(project, entry.services.clone());
+                    if entry.git_updated.is_some() {
+                        probes.insert_git(project, entry.git.clone());
+                    }
+                }
+                _ => missing.push(project.clone()),
+            }
+        }
+        missing
+    }
+
+    /// Probe `projects` live and store the results
+    pub fn refresh(&mut self, probes: &ProbeCache, projects: &[Project], git: bool) {
+        probes.prefetch_compose(projects);
+        if git {
+            probes.prefetch_git(projects);
+        }
+        let now = Utc::now();
+        for project in projects {
+            let entry = self.entries.entry(project.name.clone()).or_default();
+            entry.services = probes.compose_status(project);
+            entry.services_updated = Some(now);
+            if git {
+                entry.git = probes.git_info(project);
+                entry.git_updated = Some(now);
+            }
+        }
+    }
+
+    /// Probe only the git state of `projects` live and store it
+    pub fn refresh_git(&mut self, probes: &ProbeCache, projects: &[Project]) {
+        probes.reprobe_git(projects);
+        let now = Utc::now();
+        for project in projects {
+            let entry = self.entries.entry(project.name.clone()).or_default();
+            entry.git = probes.git_info(project);
+            entry.git_updated = Some(now);
+        }
+    }
+
+    pub fn record_outdated(&mut self, name: &str, count: usize) {
+        let entry = self.entries.entry(name.to_string()).or_default();
+        entry.outdated = Some(count);
+        entry.outdated_updated = Some(Utc::now());
+    }
+
+    /// Whether the cached service state of a project is older than `STALE_AFTER_MINUTES`
+    pub fn is_stale(&self, name: &str) -> bool {
+        self.entries
+            .get(name)
+            .and_then(|e| e.services_updated)
+            .map(|t| Utc::now().signed_duration_since(t) > Duration::minutes(STALE_AFTER_MINUTES))
+            .unwrap_or(false)
+    }
+
+    /// Drop entries of projects that are no longer registered
+    pub fn retain_projects(&mut self, projects: &[Project]) {
+        self.entries
+            .retain(|name, _| projects.iter().any(|p| &p.name == name));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
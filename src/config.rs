@@ -1,7 +1,7 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::project::Project;
 
@@ -55,6 +55,8 @@ impl ConfigManager {
             .context("Failed to create config directory")?;
         fs::create_dir_all(self.config_dir.join("templates"))
             .context("Failed to create templates directory")?;
+        fs::create_dir_all(self.cache_dir())
+            .context("Failed to create cache directory")?;
         Ok(())
     }
 
@@ -63,6 +65,10 @@ impl ConfigManager {
         self.config_dir.join("config.toml")
     }
 
+    pub fn detectors_path(&self) -> PathBuf {
+        self.config_dir.join("detectors.toml")
+    }
+
     pub fn projects_path(&self) -> PathBuf {
         self.config_dir.join("projects.toml")
     }
@@ -71,6 +77,26 @@ impl ConfigManager {
         self.config_dir.join("templates")
     }
 
+    pub fn cache_dir(&self) -> PathBuf {
+        self.config_dir.join("cache")
+    }
+
+    pub fn backups_dir(&self) -> PathBuf {
+        self.config_dir.join("backups")
+    }
+
+    pub fn logs_dir(&self) -> PathBuf {
+        self.config_dir.join("logs")
+    }
+
+    pub fn history_path(&self) -> PathBuf {
+        self.config_dir.join("history.jsonl")
+    }
+
+    pub fn activity_path(&self) -> PathBuf {
+        self.config_dir.join("activity.jsonl")
+    }
+
     #[allow(dead_code)]
     pub fn load_global_config(&self) -> Result<GlobalConfig> {
         let path = self.config_path();
@@ -145,6 +171,125 @@ impl ConfigManager {
         None
     }
 
+    /// The registered project containing `dir`: the one whose path is the
+    /// longest ancestor of it
+    pub fn find_project_by_path<'a>(
+        &self,
+        projects: &'a [Project],
+        dir: &Path,
+    ) -> Option<&'a Project> {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        projects
+            .iter()
+            .map(|p| {
+                let path = p.expanded_path();
+                (p, path.canonicalize().unwrap_or(path))
+            })
+            .filter(|(_, path)| dir.starts_with(path))
+            .max_by_key(|(_, path)| path.components().count())
+            .map(|(p, _)| p)
+    }
+
+    /// Look up a project by name, or the one containing the current
+    /// directory when no name is given. `project:package` names and
+    /// directories inside a package resolve to the sub-project,
+    /// `project@branch` names and worktree directories to the worktree.
+    pub fn resolve_project(&self, projects: &[Project], name: Option<&str>) -> Result<Project> {
+        let Some(name) = name else {
+            let cwd = std::env::current_dir().context("Failed to read current directory")?;
+            let roots: Vec<Project> = projects
+                .iter()
+                .flat_map(|p| std::iter::once(p.clone()).chain(p.worktree_projects()))
+                .collect();
+            let Some(root) = self.find_project_by_path(&roots, &cwd) else {
+                bail!(
+                    "{} is not inside a registered project. Pass a project name, or register it with 'projectctl add'.",
+                    cwd.display()
+                );
+            };
+            let subs = root.sub_projects();
+            let sub = self.find_project_by_path(&subs, &cwd).cloned();
+            return Ok(sub.unwrap_or_else(|| root.clone()));
+        };
+
+        let (root_name, package) = match name.split_once(':') {
+            Some((root, package)) => (root, Some(package)),
+            None => (name, None),
+        };
+        let (root_name, branch) = match root_name.split_once('@') {
+            Some((root, branch)) => (root, Some(branch)),
+            None => (root_name, None),
+        };
+        let root = self
+            .find_project(projects, root_name)
+            .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", root_name))?;
+        let root = match branch {
+            Some(branch) => self.resolve_worktree(root, branch)?,
+            None => root.clone(),
+        };
+        let Some(package) = package else {
+            return Ok(root);
+        };
+
+        let subs = root.sub_projects();
+        let names: Vec<String> = subs
+            .iter()
+            .map(|p| p.name[root.name.len() + 1..].to_string())
+            .collect();
+        let idx = names
+            .iter()
+            .position(|n| n == package)
+            .or_else(|| {
+                let matches: Vec<usize> = (0..names.len())
+                    .filter(|&i| names[i].starts_with(package))
+                    .collect();
+                (matches.len() == 1).then(|| matches[0])
+            });
+        match idx {
+            Some(idx) => Ok(subs[idx].clone()),
+            None if names.is_empty() => bail!("Project '{}' has no packages", root.name),
+            None => bail!(
+                "Package '{}' not found in '{}'. Packages: {}",
+                package,
+                root.name,
+                names.join(", ")
+            ),
+        }
+    }
+
+    /// The worktree of `branch` (or of the only branch starting with it)
+    fn resolve_worktree(&self, root: &Project, branch: &str) -> Result<Project> {
+        let worktrees = root.worktree_projects();
+        let branches: Vec<&str> = worktrees
+            .iter()
+            .map(|p| &p.name[root.name.len() + 1..])
+            .collect();
+        let idx = branches
+            .iter()
+            .position(|b| *b == branch)
+            .or_else(|| {
+                let matches: Vec<usize> = (0..branches.len())
+                    .filter(|&i| branches[i].starts_with(branch))
+                    .collect();
+                (matches.len() == 1).then(|| matches[0])
+            });
+        match idx {
+            Some(idx) => Ok(worktrees[idx].clone()),
+            None if branches.is_empty() => bail!(
+                "Project '{}' has no worktrees. Create one with 'projectctl worktree add {} {}'",
+                root.name,
+                root.name,
+                branch
+            ),
+            None => bail!(
+                "No worktree for branch '{}' in '{}'. Worktrees: {}",
+                branch,
+                root.name,
+                branches.join(", ")
+            ),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn find_project_mut<'a>(
         &self,
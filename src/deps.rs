@@ -5,17 +5,16 @@ use std::process::Command;
 
 use crate::project::Project;
 
-/// Dependency info for a project
-#[allow(dead_code)]
-#[derive(Debug)]
+/// Outdated-package report of one package manager
+#[derive(Debug, Clone)]
 pub struct DepsInfo {
     pub manager: String,
-    pub total_packages: usize,
+    /// False when the manager's executable could not be run
+    pub available: bool,
     pub outdated_packages: Vec<OutdatedPackage>,
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OutdatedPackage {
     pub name: String,
     pub current: String,
@@ -57,17 +56,39 @@ pub fn detect_managers(project_path: &Path) -> Vec<String> {
     managers
 }
 
-/// Check for outdated packages
-pub fn check_outdated(project: &Project) -> Result<()> {
+/// Query every detected package manager for outdated packages, without printing
+pub fn collect_outdated(project: &Project) -> Result<Vec<DepsInfo>> {
     let project_path = project.expanded_path();
     if !project_path.exists() {
         bail!("Project directory does not exist: {}", project.path);
     }
 
-    let managers = detect_managers(&project_path);
-    if managers.is_empty() {
+    let mut report = Vec::new();
+    for manager in detect_managers(&project_path) {
+        let outdated = match manager.as_str() {
+            "cargo" => check_cargo_outdated(&project_path),
+            "npm" => check_npm_outdated(&project_path),
+            "yarn" => check_yarn_outdated(&project_path),
+            "pnpm" => check_pnpm_outdated(&project_path),
+            "pip" => check_pip_outdated(&project_path),
+            "poetry" => check_poetry_outdated(&project_path),
+            "go" => check_go_outdated(&project_path),
+            _ => continue,
+        };
+        report.push(DepsInfo {
+            manager,
+            available: outdated.is_some(),
+            outdated_packages: outdated.unwrap_or_default(),
+        });
+    }
+    Ok(report)
+}
+
+/// Print an outdated-package report collected by `collect_outdated`
+pub fn print_outdated(project: &Project, report: &[DepsInfo]) {
+    if report.is_empty() {
         println!("{}", "No package managers detected.".yellow());
-        return Ok(());
+        return;
     }
 
     println!(
@@ -75,20 +96,50 @@ pub fn check_outdated(project: &Project) -> Result<()> {
         project.name.cyan().bold()
     );
 
-    for manager in &managers {
-        match manager.as_str() {
-            "cargo" => check_cargo_outdated(&project_path)?,
-            "npm" => check_npm_outdated(&project_path)?,
-            "yarn" => check_yarn_outdated(&project_path)?,
-            "pnpm" => check_pnpm_outdated(&project_path)?,
-            "pip" => check_pip_outdated(&project_path)?,
-            "poetry" => check_poetry_outdated(&project_path)?,
-            "go" => check_go_outdated(&project_path)?,
-            _ => {}
+    for info in report {
+        let (role, label) = manager_label(&info.manager);
+        println!("  {} ({}):", role.bold(), label);
+        if !info.available {
+            println!("    {} {} not available", "✗".red(), info.manager);
+            continue;
+        }
+        let outdated = &info.outdated_packages;
+        if outdated.is_empty() {
+            println!("    {} All dependencies up to date", "✓".green());
+            continue;
+        }
+        println!("    {} {} outdated package(s)", "⬆".yellow(), outdated.len());
+        for pkg in outdated.iter().take(10) {
+            println!(
+                "      {}: {} → {}",
+                pkg.name,
+                pkg.current.dimmed(),
+                pkg.latest.green()
+            );
+        }
+        if outdated.len() > 10 {
+            println!("      ... and {} more", outdated.len() - 10);
         }
     }
+}
 
-    Ok(())
+/// Total number of outdated packages across all managers in a report
+pub fn outdated_count(report: &[DepsInfo]) -> usize {
+    report.iter().map(|d| d.outdated_packages.len()).sum()
+}
+
+/// Section heading and ecosystem label for a package manager
+fn manager_label(manager: &str) -> (&'static str, &'static str) {
+    match manager {
+        "cargo" => ("Backend", "Rust/Cargo"),
+        "npm" => ("Frontend", "Node/npm"),
+        "yarn" => ("Frontend", "Node/yarn"),
+        "pnpm" => ("Frontend", "Node/pnpm"),
+        "pip" => ("Backend", "Python/pip"),
+        "poetry" => ("Backend", "Python/poetry"),
+        "go" => ("Modules", "Go"),
+        _ => ("Packages", "other"),
+    }
 }
 
 /// Update dependencies
@@ -151,31 +202,31 @@ pub fn show_summary(projects: &[Project]) -> Result<()> {
 
 // --- Cargo ---
 
-fn check_cargo_outdated(path: &Path) -> Result<()> {
-    println!("  {} (Rust/Cargo):", "Backend".bold());
-    let output = Command::new("cargo")
+fn check_cargo_outdated(path: &Path) -> Option<Vec<OutdatedPackage>> {
+    let out = Command::new("cargo")
         .args(["update", "--dry-run"])
         .current_dir(path)
-        .output();
-
-    match output {
-        Ok(out) => {
-            let stderr = String::from_utf8_lossy(&out.stderr);
-            let updating_lines: Vec<&str> = stderr
-                .lines()
-                .filter(|l| l.contains("Updating") || l.contains("updating"))
-                .collect();
-            if updating_lines.is_empty() {
-                println!("    {} All dependencies up to date", "✓".green());
-            } else {
-                for line in &updating_lines {
-                    println!("    {}", line.trim().yellow());
-                }
+        .output()
+        .ok()?;
+
+    // Lines look like: "    Updating serde v1.0.190 -> v1.0.193"
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let outdated = stderr
+        .lines()
+        .filter(|l| l.contains("Updating") || l.contains("updating"))
+        .filter_map(|l| {
+            let parts: Vec<&str> = l.split_whitespace().collect();
+            match parts.as_slice() {
+                [_, name, current, "->", latest, ..] => Some(OutdatedPackage {
+                    name: name.to_string(),
+                    current: current.to_string(),
+                    latest: latest.to_string(),
+                }),
+                _ => None,
             }
-        }
-        Err(_) => println!("    {} cargo not available", "✗".red()),
-    }
-    Ok(())
+        })
+        .collect();
+    Some(outdated)
 }
 
 fn update_cargo(path: &Path) -> Result<()> {
@@ -197,41 +248,30 @@ fn update_cargo(path: &Path) -> Result<()> {
 
 // --- npm ---
 
-fn check_npm_outdated(path: &Path) -> Result<()> {
-    println!("  {} (Node/npm):", "Frontend".bold());
-    let output = Command::new("npm")
+fn check_npm_outdated(path: &Path) -> Option<Vec<OutdatedPackage>> {
+    let out = Command::new("npm")
         .args(["outdated", "--json"])
         .current_dir(path)
-        .output();
-
-    match output {
-        Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            if stdout.trim().is_empty() || stdout.trim() == "{}" {
-                println!("    {} All dependencies up to date", "✓".green());
-            } else if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&stdout) {
-                if let Some(obj) = parsed.as_object() {
-                    let count = obj.len();
-                    println!("    {} {} outdated package(s)", "⬆".yellow(), count);
-                    for (name, info) in obj.iter().take(10) {
-                        let current = info["current"].as_str().unwrap_or("?");
-                        let latest = info["latest"].as_str().unwrap_or("?");
-                        println!(
-                            "      {}: {} → {}",
-                            name,
-                            current.dimmed(),
-                            latest.green()
-                        );
-                    }
-                    if count > 10 {
-                        println!("      ... and {} more", count - 10);
-                    }
-                }
-            }
-        }
-        Err(_) => println!("    {} npm not available", "✗".red()),
-    }
-    Ok(())
+        .output()
+        .ok()?;
+    Some(parse_outdated_json_object(&String::from_utf8_lossy(&out.stdout)))
+}
+
+/// Parse the `{ "<name>": { "current": ..., "latest": ... } }` shape used by npm and pnpm
+fn parse_outdated_json_object(stdout: &str) -> Vec<OutdatedPackage> {
+    let Ok(parsed) = serde_json::from_str::<serde_json::Value>(stdout) else {
+        return Vec::new();
+    };
+    let Some(obj) = parsed.as_object() else {
+        return Vec::new();
+    };
+    obj.iter()
+        .map(|(name, info)| OutdatedPackage {
+            name: name.clone(),
+            current: info["current"].as_str().unwrap_or("?").to_string(),
+            latest: info["latest"].as_str().unwrap_or("?").to_string(),
+        })
+        .collect()
 }
 
 fn update_npm(path: &Path) -> Result<()> {
@@ -253,27 +293,34 @@ fn update_npm(path: &Path) -> Result<()> {
 
 // --- yarn ---
 
-fn check_yarn_outdated(path: &Path) -> Result<()> {
-    println!("  {} (Node/yarn):", "Frontend".bold());
-    let output = Command::new("yarn")
-        .args(["outdated"])
+fn check_yarn_outdated(path: &Path) -> Option<Vec<OutdatedPackage>> {
+    let out = Command::new("yarn")
+        .args(["outdated", "--json"])
         .current_dir(path)
-        .output();
-
-    match output {
-        Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            if stdout.trim().is_empty() {
-                println!("    {} All dependencies up to date", "✓".green());
-            } else {
-                let lines: Vec<&str> = stdout.lines().collect();
-                let count = lines.len().saturating_sub(1); // header line
-                println!("    {} {} outdated package(s)", "⬆".yellow(), count);
-            }
+        .output()
+        .ok()?;
+
+    // One JSON event per line; the outdated packages are in the "table" event,
+    // with rows of [name, current, wanted, latest, ...]
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let mut outdated = Vec::new();
+    for line in stdout.lines() {
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if event["type"] != "table" {
+            continue;
+        }
+        for row in event["data"]["body"].as_array().into_iter().flatten() {
+            let cell = |i: usize| row[i].as_str().unwrap_or("?").to_string();
+            outdated.push(OutdatedPackage {
+                name: cell(0),
+                current: cell(1),
+                latest: cell(3),
+            });
         }
-        Err(_) => println!("    {} yarn not available", "✗".red()),
     }
-    Ok(())
+    Some(outdated)
 }
 
 fn update_yarn(path: &Path) -> Result<()> {
@@ -295,25 +342,13 @@ fn update_yarn(path: &Path) -> Result<()> {
 
 // --- pnpm ---
 
-fn check_pnpm_outdated(path: &Path) -> Result<()> {
-    println!("  {} (Node/pnpm):", "Frontend".bold());
-    let output = Command::new("pnpm")
-        .args(["outdated"])
+fn check_pnpm_outdated(path: &Path) -> Option<Vec<OutdatedPackage>> {
+    let out = Command::new("pnpm")
+        .args(["outdated", "--format", "json"])
         .current_dir(path)
-        .output();
-
-    match output {
-        Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            if stdout.trim().is_empty() {
-                println!("    {} All dependencies up to date", "✓".green());
-            } else {
-                println!("    {}", stdout.trim());
-            }
-        }
-        Err(_) => println!("    {} pnpm not available", "✗".red()),
-    }
-    Ok(())
+        .output()
+        .ok()?;
+    Some(parse_outdated_json_object(&String::from_utf8_lossy(&out.stdout)))
 }
 
 fn update_pnpm(path: &Path) -> Result<()> {
@@ -335,45 +370,24 @@ fn update_pnpm(path: &Path) -> Result<()> {
 
 // --- pip ---
 
-fn check_pip_outdated(path: &Path) -> Result<()> {
-    println!("  {} (Python/pip):", "Backend".bold());
-    let output = Command::new("pip")
+fn check_pip_outdated(path: &Path) -> Option<Vec<OutdatedPackage>> {
+    let out = Command::new("pip")
         .args(["list", "--outdated", "--format", "json"])
         .current_dir(path)
-        .output();
-
-    match output {
-        Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            if let Ok(parsed) = serde_json::from_str::<Vec<serde_json::Value>>(&stdout) {
-                if parsed.is_empty() {
-                    println!("    {} All dependencies up to date", "✓".green());
-                } else {
-                    println!(
-                        "    {} {} outdated package(s)",
-                        "⬆".yellow(),
-                        parsed.len()
-                    );
-                    for pkg in parsed.iter().take(10) {
-                        let name = pkg["name"].as_str().unwrap_or("?");
-                        let current = pkg["version"].as_str().unwrap_or("?");
-                        let latest = pkg["latest_version"].as_str().unwrap_or("?");
-                        println!(
-                            "      {}: {} → {}",
-                            name,
-                            current.dimmed(),
-                            latest.green()
-                        );
-                    }
-                    if parsed.len() > 10 {
-                        println!("      ... and {} more", parsed.len() - 10);
-                    }
-                }
-            }
-        }
-        Err(_) => println!("    {} pip not available", "✗".red()),
-    }
-    Ok(())
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let parsed = serde_json::from_str::<Vec<serde_json::Value>>(&stdout).unwrap_or_default();
+    let outdated = parsed
+        .iter()
+        .map(|pkg| OutdatedPackage {
+            name: pkg["name"].as_str().unwrap_or("?").to_string(),
+            current: pkg["version"].as_str().unwrap_or("?").to_string(),
+            latest: pkg["latest_version"].as_str().unwrap_or("?").to_string(),
+        })
+        .collect();
+    Some(outdated)
 }
 
 fn update_pip(path: &Path) -> Result<()> {
@@ -401,26 +415,30 @@ fn update_pip(path: &Path) -> Result<()> {
 
 // --- poetry ---
 
-fn check_poetry_outdated(path: &Path) -> Result<()> {
-    println!("  {} (Python/poetry):", "Backend".bold());
-    let output = Command::new("poetry")
+fn check_poetry_outdated(path: &Path) -> Option<Vec<OutdatedPackage>> {
+    let out = Command::new("poetry")
         .args(["show", "--outdated"])
         .current_dir(path)
-        .output();
-
-    match output {
-        Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            if stdout.trim().is_empty() {
-                println!("    {} All dependencies up to date", "✓".green());
-            } else {
-                let count = stdout.lines().count();
-                println!("    {} {} outdated package(s)", "⬆".yellow(), count);
+        .output()
+        .ok()?;
+
+    // Lines look like: "fastapi   0.109.0 0.110.0 FastAPI framework, ..."
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let outdated = stdout
+        .lines()
+        .filter_map(|l| {
+            let parts: Vec<&str> = l.split_whitespace().filter(|p| *p != "(!)").collect();
+            match parts.as_slice() {
+                [name, current, latest, ..] => Some(OutdatedPackage {
+                    name: name.to_string(),
+                    current: current.to_string(),
+                    latest: latest.to_string(),
+                }),
+                _ => None,
             }
-        }
-        Err(_) => println!("    {} poetry not available", "✗".red()),
-    }
-    Ok(())
+        })
+        .collect();
+    Some(outdated)
 }
 
 fn update_poetry(path: &Path) -> Result<()> {
@@ -442,36 +460,31 @@ fn update_poetry(path: &Path) -> Result<()> {
 
 // --- go ---
 
-fn check_go_outdated(path: &Path) -> Result<()> {
-    println!("  {} (Go):", "Modules".bold());
-    let output = Command::new("go")
+fn check_go_outdated(path: &Path) -> Option<Vec<OutdatedPackage>> {
+    let out = Command::new("go")
         .args(["list", "-m", "-u", "all"])
         .current_dir(path)
-        .output();
-
-    match output {
-        Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            let outdated: Vec<&str> = stdout
-                .lines()
-                .filter(|l| l.contains('['))
-                .collect();
-            if outdated.is_empty() {
-                println!("    {} All modules up to date", "✓".green());
-            } else {
-                println!(
-                    "    {} {} outdated module(s)",
-                    "⬆".yellow(),
-                    outdated.len()
-                );
-                for line in outdated.iter().take(10) {
-                    println!("      {}", line.trim());
-                }
+        .output()
+        .ok()?;
+
+    // Lines with an available update look like: "golang.org/x/text v0.3.0 [v0.14.0]"
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let outdated = stdout
+        .lines()
+        .filter(|l| l.contains('['))
+        .filter_map(|l| {
+            let parts: Vec<&str> = l.split_whitespace().collect();
+            match parts.as_slice() {
+                [name, current, latest, ..] => Some(OutdatedPackage {
+                    name: name.to_string(),
+                    current: current.to_string(),
+                    latest: latest.trim_matches(|c| c == '[' || c == ']').to_string(),
+                }),
+                _ => None,
             }
-        }
-        Err(_) => println!("    {} go not available", "✗".red()),
-    }
-    Ok(())
+        })
+        .collect();
+    Some(outdated)
 }
 
 fn update_go(path: &Path) -> Result<()> {
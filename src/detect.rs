@@ -0,0 +1,543 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::config::ConfigManager;
+use crate::project::parse_yaml_section_keys;
+
+/// Built-in detection rules, in the same format as ~/.projectctl/detectors.toml
+const BUILTIN_DETECTORS: &str = r#"
+[[detector]]
+type = "tauri"
+stack = "rust"
+priority = 90
+files = ["src-tauri"]
+
+[[detector]]
+type = "rust"
+stack = "rust"
+priority = 70
+files = ["Cargo.toml"]
+
+[[detector]]
+type = "fastapi"
+stack = "api"
+priority = 50
+dependencies = ["fastapi"]
+
+[[detector]]
+type = "django"
+stack = "api"
+priority = 50
+dependencies = ["django"]
+
+[[detector]]
+type = "django"
+stack = "api"
+priority = 45
+files = ["manage.py"]
+
+[[detector]]
+type = "flask"
+stack = "api"
+priority = 50
+dependencies = ["flask"]
+
+[[detector]]
+type = "python"
+stack = "api"
+priority = 10
+files = ["pyproject.toml"]
+
+[[detector]]
+type = "python"
+stack = "api"
+priority = 10
+files = ["setup.py"]
+
+[[detector]]
+type = "python"
+stack = "api"
+priority = 5
+files = ["requirements.txt"]
+
+[[detector]]
+type = "nextjs"
+stack = "web"
+priority = 60
+dependencies = ["next"]
+
+[[detector]]
+type = "nuxt"
+stack = "web"
+priority = 60
+dependencies = ["nuxt"]
+
+[[detector]]
+type = "astro"
+stack = "web"
+priority = 60
+dependencies = ["astro"]
+
+[[detector]]
+type = "remix"
+stack = "web"
+priority = 60
+dependencies = ["@remix-run/react", "@remix-run/node"]
+
+[[detector]]
+type = "sveltekit"
+stack = "web"
+priority = 60
+keys = ["package.json:devDependencies.@sveltejs/kit", "package.json:dependencies.@sveltejs/kit"]
+
+[[detector]]
+type = "react-vite"
+stack = "web"
+priority = 45
+dependencies = ["react"]
+keys = ["package.json:devDependencies.vite", "package.json:dependencies.vite"]
+
+[[detector]]
+type = "react"
+stack = "web"
+priority = 40
+dependencies = ["react"]
+
+[[detector]]
+type = "vue"
+stack = "web"
+priority = 40
+dependencies = ["vue"]
+
+[[detector]]
+type = "svelte"
+stack = "web"
+priority = 35
+keys = ["package.json:devDependencies.svelte", "package.json:dependencies.svelte"]
+
+[[detector]]
+type = "express"
+stack = "web"
+priority = 30
+dependencies = ["express"]
+
+[[detector]]
+type = "node"
+stack = "web"
+priority = 5
+files = ["package.json"]
+
+[[detector]]
+type = "flutter"
+stack = "dart"
+priority = 50
+files = ["pubspec.yaml"]
+dependencies = ["flutter"]
+
+[[detector]]
+type = "dart"
+stack = "dart"
+priority = 10
+files = ["pubspec.yaml"]
+
+[[detector]]
+type = "phoenix"
+stack = "elixir"
+priority = 50
+files = ["mix.exs"]
+dependencies = ["phoenix"]
+
+[[detector]]
+type = "elixir"
+stack = "elixir"
+priority = 10
+files = ["mix.exs"]
+
+[[detector]]
+type = "dotnet"
+stack = "dotnet"
+priority = 10
+files = ["*.sln"]
+
+[[detector]]
+type = "dotnet"
+stack = "dotnet"
+priority = 10
+files = ["*.csproj"]
+
+[[detector]]
+type = "dotnet"
+stack = "dotnet"
+priority = 10
+files = ["*.fsproj"]
+
+[[detector]]
+type = "go"
+stack = "go"
+priority = 10
+files = ["go.mod"]
+
+[[detector]]
+type = "java"
+stack = "java"
+priority = 10
+files = ["pom.xml"]
+
+[[detector]]
+type = "java"
+stack = "java"
+priority = 10
+files = ["build.gradle"]
+
+[[detector]]
+type = "java"
+stack = "java"
+priority = 10
+files = ["build.gradle.kts"]
+"#;
+
+/// A project type detection rule. Every condition that is set must hold;
+/// within `keys` and `dependencies` one match is enough.
+///
+/// ```toml
+/// [[detector]]
+/// type = "remix"
+/// stack = "web"
+/// priority = 60
+/// files = ["remix.config.js"]
+/// keys = ["package.json:dependencies.@remix-run/react"]
+/// dependencies = ["@remix-run/node"]
+/// ```
+#[derive(Debug, Deserialize, Clone)]
+pub struct Detector {
+    #[serde(rename = "type")]
+    pub project_type: String,
+    /// Part of a polyglot project the rule describes (`web`, `api`, `rust`,
+    /// ...). One type is detected per stack. Defaults to the type.
+    #[serde(default)]
+    pub stack: Option<String>,
+    /// The matching rule with the highest priority wins
+    #[serde(default)]
+    pub priority: i32,
+    /// Files or directories that must exist; `*` matches in file names
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// `file:dotted.key.path` into a JSON or TOML manifest
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// Runtime dependencies declared in package.json, pyproject.toml,
+    /// requirements.txt, Cargo.toml, pubspec.yaml or mix.exs
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Where the rule was defined (set when loading)
+    #[serde(skip)]
+    pub source: String,
+}
+
+#[derive(Deserialize)]
+struct DetectorsFile {
+    #[serde(default)]
+    detector: Vec<Detector>,
+}
+
+/// Outcome of matching one rule against a directory
+pub struct RuleMatch<'a> {
+    pub detector: &'a Detector,
+    pub matched: bool,
+    /// The conditions that held, e.g. `package.json:dependencies.next`
+    pub reasons: Vec<String>,
+}
+
+/// User rules from ~/.projectctl/detectors.toml followed by the built-in ones
+pub fn detectors() -> &'static [Detector] {
+    static DETECTORS: OnceLock<Vec<Detector>> = OnceLock::new();
+    DETECTORS.get_or_init(|| {
+        let mut detectors = match load_user_detectors() {
+            Ok(detectors) => detectors,
+            Err(e) => {
+                eprintln!("Warning: ignoring detectors.toml: {:#}", e);
+                Vec::new()
+            }
+        };
+        let builtin: DetectorsFile =
+            toml::from_str(BUILTIN_DETECTORS).expect("built-in detectors are valid");
+        detectors.extend(builtin.detector.into_iter().map(|mut d| {
+            d.source = "built-in".to_string();
+            d
+        }));
+        detectors
+    })
+}
+
+fn load_user_detectors() -> Result<Vec<Detector>> {
+    let config = ConfigManager::new()?;
+    let path = config.detectors_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path).context("Failed to read detectors.toml")?;
+    let file: DetectorsFile = toml::from_str(&content).context("Failed to parse detectors.toml")?;
+    Ok(file
+        .detector
+        .into_iter()
+        .map(|mut d| {
+            d.source = path.display().to_string();
+            d
+        })
+        .collect())
+}
+
+/// Evaluate every rule against `path`, best match first
+pub fn explain(path: &Path) -> Vec<RuleMatch<'static>> {
+    let manifests = Manifests::read(path);
+    let mut matches: Vec<RuleMatch> = detectors()
+        .iter()
+        .map(|d| evaluate(d, path, &manifests))
+        .collect();
+    // Stable sort keeps user rules ahead of built-in ones on equal priority
+    matches.sort_by(|a, b| {
+        b.matched
+            .cmp(&a.matched)
+            .then(b.detector.priority.cmp(&a.detector.priority))
+    });
+    matches
+}
+
+/// Secondary stacks need more than a bare manifest: a package.json next to
+/// a Cargo.toml is usually just tooling
+const SECONDARY_MIN_PRIORITY: i32 = 10;
+
+/// One detected stack of a (possibly polyglot) project
+#[derive(Debug, Clone)]
+pub struct DetectedStack {
+    /// Stack name, used to namespace commands (`web:dev`)
+    pub name: String,
+    pub project_type: String,
+}
+
+impl Detector {
+    pub fn stack_name(&self) -> &str {
+        self.stack.as_deref().unwrap_or(&self.project_type)
+    }
+}
+
+/// The project type of the directory, or "unknown"
+pub fn detect(path: &Path) -> String {
+    detect_stacks(path)
+        .into_iter()
+        .next()
+        .map_or_else(|| "unknown".to_string(), |s| s.project_type)
+}
+
+/// The best matching type of each stack, primary (highest priority) first
+pub fn detect_stacks(path: &Path) -> Vec<DetectedStack> {
+    stacks_of(&explain(path))
+}
+
+/// Pick the stacks from `explain` results
+pub fn stacks_of(matches: &[RuleMatch]) -> Vec<DetectedStack> {
+    let mut stacks: Vec<DetectedStack> = Vec::new();
+    for m in matches.iter().filter(|m| m.matched) {
+        let name = m.detector.stack_name();
+        let secondary = !stacks.is_empty();
+        if stacks.iter().any(|s| s.name == name)
+            || (secondary && m.detector.priority < SECONDARY_MIN_PRIORITY)
+        {
+            continue;
+        }
+        stacks.push(DetectedStack {
+            name: name.to_string(),
+            project_type: m.detector.project_type.clone(),
+        });
+    }
+    stacks
+}
+
+fn evaluate<'a>(detector: &'a Detector, path: &Path, manifests: &Manifests) -> RuleMatch<'a> {
+    let mut reasons = Vec::new();
+    let has_condition = !detector.files.is_empty()
+        || !detector.keys.is_empty()
+        || !detector.dependencies.is_empty();
+    let mut matched = has_condition;
+
+    for file in &detector.files {
+        if file_exists(path, file) {
+            reasons.push(file.clone());
+        } else {
+            matched = false;
+        }
+    }
+
+    if !detector.keys.is_empty() {
+        match detector.keys.iter().find(|k| manifests.has_key(k)) {
+            Some(key) => reasons.push(key.clone()),
+            None => matched = false,
+        }
+    }
+
+    if !detector.dependencies.is_empty() {
+        match detector
+            .dependencies
+            .iter()
+            .find(|d| manifests.dependencies.contains(&d.to_lowercase()))
+        {
+            Some(dep) => reasons.push(format!("dependency {}", dep)),
+            None => matched = false,
+        }
+    }
+
+    RuleMatch {
+        detector,
+        matched,
+        reasons,
+    }
+}
+
+/// Whether `pattern` names an existing entry of `dir` (`*` allowed in the
+/// last component, e.g. `*.csproj`)
+fn file_exists(dir: &Path, pattern: &str) -> bool {
+    let Some((prefix, suffix)) = pattern.split_once('*') else {
+        return dir.join(pattern).exists();
+    };
+    let (subdir, prefix) = match prefix.rsplit_once('/') {
+        Some((subdir, prefix)) => (dir.join(subdir), prefix),
+        None => (dir.to_path_buf(), prefix),
+    };
+    let Ok(entries) = std::fs::read_dir(subdir) else {
+        return false;
+    };
+    entries.flatten().any(|e| {
+        let name = e.file_name().to_string_lossy().to_string();
+        name.len() >= prefix.len() + suffix.len()
+            && name.starts_with(prefix)
+            && name.ends_with(suffix)
+    })
+}
+
+/// Parsed manifests of a directory
+struct Manifests {
+    package_json: Option<serde_json::Value>,
+    pyproject: Option<toml::Value>,
+    cargo: Option<toml::Value>,
+    /// Lowercased names of declared runtime dependencies
+    dependencies: HashSet<String>,
+}
+
+impl Manifests {
+    fn read(path: &Path) -> Self {
+        let read = |file: &str| std::fs::read_to_string(path.join(file)).ok();
+        let package_json: Option<serde_json::Value> =
+            read("package.json").and_then(|c| serde_json::from_str(&c).ok());
+        let pyproject = read("pyproject.toml").and_then(|c| c.parse::<toml::Table>().ok());
+        let cargo = read("Cargo.toml").and_then(|c| c.parse::<toml::Table>().ok());
+
+        let mut dependencies = HashSet::new();
+        if let Some(json) = &package_json {
+            if let Some(deps) = json["dependencies"].as_object() {
+                dependencies.extend(deps.keys().cloned());
+            }
+        }
+        if let Some(pyproject) = &pyproject {
+            if let Some(deps) = pyproject
+                .get("project")
+                .and_then(|p| p.get("dependencies"))
+                .and_then(|d| d.as_array())
+            {
+                dependencies.extend(deps.iter().filter_map(|d| d.as_str()).map(requirement_name));
+            }
+            if let Some(deps) = pyproject
+                .get("tool")
+                .and_then(|t| t.get("poetry"))
+                .and_then(|p| p.get("dependencies"))
+                .and_then(|d| d.as_table())
+            {
+                dependencies.extend(deps.keys().cloned());
+            }
+        }
+        if let Some(content) = read("requirements.txt") {
+            dependencies.extend(
+                content
+                    .lines()
+                    .map(|l| l.trim())
+                    .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with('-'))
+                    .map(requirement_name),
+            );
+        }
+        if let Some(deps) = cargo
+            .as_ref()
+            .and_then(|c| c.get("dependencies"))
+            .and_then(|d| d.as_table())
+        {
+            dependencies.extend(deps.keys().cloned());
+        }
+        if let Some(content) = read("pubspec.yaml") {
+            dependencies.extend(parse_yaml_section_keys(&content, "dependencies"));
+        }
+        if let Some(content) = read("mix.exs") {
+            // Dependency tuples look like `{:phoenix, "~> 1.7"}`
+            dependencies.extend(content.split("{:").skip(1).filter_map(|rest| {
+                let name = rest.split(',').next()?.trim();
+                let valid = !name.is_empty()
+                    && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+                valid.then(|| name.to_string())
+            }));
+        }
+
+        Self {
+            package_json,
+            pyproject: pyproject.map(toml::Value::Table),
+            cargo: cargo.map(toml::Value::Table),
+            dependencies: dependencies.into_iter().map(|d| d.to_lowercase()).collect(),
+        }
+    }
+
+    /// Whether a `file:dotted.key.path` is present
+    fn has_key(&self, spec: &str) -> bool {
+        let Some((file, key_path)) = spec.split_once(':') else {
+            return false;
+        };
+        let keys = key_path.split('.');
+        match file {
+            "package.json" => self.package_json.as_ref().is_some_and(|json| {
+                let mut value = json;
+                for key in keys {
+                    match value.get(key) {
+                        Some(v) => value = v,
+                        None => return false,
+                    }
+                }
+                true
+            }),
+            "pyproject.toml" | "Cargo.toml" => {
+                let manifest = if file == "Cargo.toml" {
+                    &self.cargo
+                } else {
+                    &self.pyproject
+                };
+                manifest.as_ref().is_some_and(|toml| {
+                    let mut value = toml;
+                    for key in keys {
+                        match value.get(key) {
+                            Some(v) => value = v,
+                            None => return false,
+                        }
+                    }
+                    true
+                })
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Package name of a PEP 508 requirement, e.g. `fastapi[all]>=0.110` → `fastapi`
+fn requirement_name(requirement: &str) -> String {
+    requirement
+        .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
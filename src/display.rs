@@ -1,11 +1,22 @@
+use chrono::{DateTime, Utc};
 use colored::Colorize;
+use std::collections::HashMap;
+use std::io::Write;
 use tabled::{
     settings::Style,
     Table, Tabled,
 };
 
+use crate::activity::{Frecency, MatchKind};
+use crate::cache::{StatusCache, STALE_AFTER_MINUTES};
+use crate::detect::RuleMatch;
+use crate::git::{BranchInfo, GitInfo, PruneCandidate};
+use crate::history::{CommandStats, RunRecord};
+use crate::probe::ProbeCache;
 use crate::project::Project;
-use crate::services;
+use crate::report::{self, ProjectReport};
+use crate::runner::BatchResult;
+use crate::services::{ContainerStats, ServiceState, ServiceStatus};
 
 /// Row in the project list table
 #[derive(Tabled)]
@@ -22,8 +33,192 @@ struct ProjectRow {
     last_used: String,
 }
 
+/// Row in the project list table with git columns (`list --git`)
+#[derive(Tabled)]
+struct GitProjectRow {
+    #[tabled(inline)]
+    project: ProjectRow,
+    #[tabled(rename = "Branch")]
+    branch: String,
+    #[tabled(rename = "Dirty")]
+    dirty: String,
+    #[tabled(rename = "Ahead/Behind")]
+    sync: String,
+}
+
+/// Row in the `ps` dashboard table
+#[derive(Tabled)]
+struct PsRow {
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Service")]
+    service: String,
+    #[tabled(rename = "State")]
+    state: String,
+    #[tabled(rename = "Ports")]
+    ports: String,
+    #[tabled(rename = "Uptime")]
+    uptime: String,
+    #[tabled(rename = "CPU")]
+    cpu: String,
+    #[tabled(rename = "Memory")]
+    memory: String,
+}
+
+/// Row in the batch run summary table
+#[derive(Tabled)]
+struct BatchRow {
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Exit")]
+    exit: String,
+    #[tabled(rename = "Duration")]
+    duration: String,
+    #[tabled(rename = "Log")]
+    log: String,
+}
+
+/// Row in the `detect --explain` table
+#[derive(Tabled)]
+struct DetectionRow {
+    #[tabled(rename = "")]
+    status: String,
+    #[tabled(rename = "Type")]
+    project_type: String,
+    #[tabled(rename = "Stack")]
+    stack: String,
+    #[tabled(rename = "Priority")]
+    priority: i32,
+    #[tabled(rename = "Matched On")]
+    reasons: String,
+    #[tabled(rename = "Rule")]
+    source: String,
+}
+
+/// Row in the summary of a multi-repo git operation
+#[derive(Tabled)]
+struct GitResultRow {
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Result")]
+    result: String,
+}
+
+/// Row in the `git branches` table
+#[derive(Tabled)]
+struct BranchRow {
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Branch")]
+    branch: String,
+    #[tabled(rename = "Last Commit")]
+    last_commit: String,
+    #[tabled(rename = "Merged")]
+    merged: String,
+    #[tabled(rename = "Upstream")]
+    upstream: String,
+}
+
+/// Row in the `worktree list` table
+#[derive(Tabled)]
+struct WorktreeRow {
+    #[tabled(rename = "Worktree")]
+    name: String,
+    #[tabled(rename = "Path")]
+    path: String,
+    #[tabled(rename = "Git")]
+    git: String,
+}
+
+/// Row in the activity report
+#[derive(Tabled)]
+struct ReportRow {
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Commits")]
+    commits: usize,
+    #[tabled(rename = "Lines")]
+    lines: String,
+    #[tabled(rename = "Active Days")]
+    active_days: usize,
+    #[tabled(rename = "Runs")]
+    runs: usize,
+    #[tabled(rename = "Run Time")]
+    run_time: String,
+    #[tabled(rename = "Last Used")]
+    last_used: String,
+}
+
+/// Row in the `switch --explain` table
+#[derive(Tabled)]
+struct SwitchMatchRow {
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Match")]
+    kind: String,
+    #[tabled(rename = "Score")]
+    score: String,
+    #[tabled(rename = "Visits")]
+    breakdown: String,
+    #[tabled(rename = "Last Used")]
+    last: String,
+}
+
+/// Row in the `git prune` report
+#[derive(Tabled)]
+struct PruneRow {
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Remove")]
+    kind: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Details")]
+    details: String,
+}
+
+/// Row in the run history table
+#[derive(Tabled)]
+struct HistoryRow {
+    #[tabled(rename = "When")]
+    when: String,
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Command")]
+    command: String,
+    #[tabled(rename = "Duration")]
+    duration: String,
+    #[tabled(rename = "Exit")]
+    exit: String,
+}
+
+/// Row in the command statistics table
+#[derive(Tabled)]
+struct StatsRow {
+    #[tabled(rename = "Project")]
+    project: String,
+    #[tabled(rename = "Command")]
+    command: String,
+    #[tabled(rename = "Runs")]
+    runs: usize,
+    #[tabled(rename = "Avg")]
+    avg: String,
+    #[tabled(rename = "Max")]
+    max: String,
+    #[tabled(rename = "Failures")]
+    failures: String,
+    #[tabled(rename = "Last Run")]
+    last_run: String,
+}
+
 /// Display the project list as a formatted table
-pub fn display_project_list(projects: &[Project], detailed: bool) {
+pub fn display_project_list(
+    projects: &[Project],
+    detailed: bool,
+    git: bool,
+    cache: &ProbeCache,
+    status_cache: &StatusCache,
+) {
     if projects.is_empty() {
         println!("{}", "No projects registered.".yellow());
         println!("Add one with: projectctl add --path /path/to/project");
@@ -36,20 +231,50 @@ pub fn display_project_list(projects: &[Project], detailed: bool) {
         .iter()
         .enumerate()
         .map(|(i, p)| {
-            let status = get_project_status(p);
+            let mut status = get_project_status(p, cache);
+            if status_cache.is_stale(&p.name) {
+                status.push_str(&" *".dimmed().to_string());
+            }
             ProjectRow {
                 index: i + 1,
                 name: p.name.clone(),
-                project_type: capitalize(&p.project_type),
+                project_type: capitalize(&p.type_label()),
                 status,
                 last_used: p.last_used_ago(),
             }
         })
         .collect();
 
-    let table = Table::new(&rows)
-        .with(Style::rounded())
-        .to_string();
+    let table = if git {
+        let rows: Vec<GitProjectRow> = projects
+            .iter()
+            .zip(rows)
+            .map(|(p, project)| match cache.git_info(p) {
+                Some(info) => {
+                    let dirty = info.dirty_count();
+                    GitProjectRow {
+                        project,
+                        branch: info.branch.clone(),
+                        dirty: if dirty == 0 {
+                            "-".dimmed().to_string()
+                        } else {
+                            dirty.to_string().yellow().to_string()
+                        },
+                        sync: info.sync_string(),
+                    }
+                }
+                None => GitProjectRow {
+                    project,
+                    branch: "-".dimmed().to_string(),
+                    dirty: String::new(),
+                    sync: String::new(),
+                },
+            })
+            .collect();
+        Table::new(&rows).with(Style::rounded()).to_string()
+    } else {
+        Table::new(&rows).with(Style::rounded()).to_string()
+    };
 
     println!("{}", table);
 
@@ -57,7 +282,7 @@ pub fn display_project_list(projects: &[Project], detailed: bool) {
     let total = projects.len();
     let active = projects
         .iter()
-        .filter(|p| is_running(p))
+        .filter(|p| cache.is_running(p))
         .count();
 
     // Count by type
@@ -81,10 +306,24 @@ pub fn display_project_list(projects: &[Project], detailed: bool) {
         type_parts.join(" | ")
     );
 
+    if projects.iter().any(|p| status_cache.is_stale(&p.name)) {
+        println!(
+            "{}",
+            format!(
+                "* cached status older than {} min, run with --refresh to update",
+                STALE_AFTER_MINUTES
+            )
+            .dimmed()
+        );
+    }
+
     if detailed {
         println!();
         for p in projects {
             println!("  {} ({})", p.name.cyan().bold(), p.path);
+            if let Some(git) = cache.git_info(p) {
+                println!("    Git:      {} ({})", git.branch.cyan(), git.summary());
+            }
             if !p.commands.is_empty() {
                 let cmds: Vec<String> = p.commands.keys().cloned().collect();
                 println!("    Commands: {}", cmds.join(", "));
@@ -92,59 +331,536 @@ pub fn display_project_list(projects: &[Project], detailed: bool) {
             if !p.services.is_empty() {
                 println!("    Services: {}", p.services.join(", "));
             }
+            if let Some(count) = status_cache.entry(&p.name).and_then(|e| e.outdated) {
+                println!("    Outdated: {} package(s)", count);
+            }
         }
     }
 }
 
 /// Display recent projects list
-pub fn display_recent(projects: &[Project], limit: usize) {
-    if projects.is_empty() {
+pub fn display_recent(ranking: &[Frecency], limit: usize) {
+    if ranking.is_empty() {
         println!("{}", "No recent projects.".yellow());
         return;
     }
 
     println!("{}\n", "Recent Projects:".bold());
 
-    let mut sorted: Vec<&Project> = projects.iter().collect();
-    sorted.sort_by(|a, b| {
-        let ta = a.last_used_time();
-        let tb = b.last_used_time();
-        tb.cmp(&ta)
-    });
-
-    for (i, p) in sorted.iter().take(limit).enumerate() {
+    for (i, f) in ranking.iter().take(limit).enumerate() {
+        let last = f.last.map_or_else(|| "never".to_string(), time_ago);
         println!(
-            "  {}. {}  ({})",
+            "  {}. {}  ({}, {} use{})",
             (i + 1).to_string().bold(),
-            p.name.cyan(),
-            p.last_used_ago()
+            f.project.cyan(),
+            last,
+            f.visits,
+            if f.visits == 1 { "" } else { "s" }
         );
     }
 
     println!("\nSwitch: projectctl switch <name>");
 }
 
+/// Display docker compose service states, one line per service
+pub fn display_services(services: &[ServiceStatus]) {
+    let width = services.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    for svc in services {
+        let icon = match svc.state {
+            ServiceState::Running => "✓".green().to_string(),
+            ServiceState::Restarting | ServiceState::Paused | ServiceState::Created => {
+                "~".yellow().to_string()
+            }
+            _ => "✗".red().to_string(),
+        };
+
+        let mut details = Vec::new();
+        if !svc.image.is_empty() {
+            details.push(svc.image.clone());
+        }
+        if !svc.ports.is_empty() {
+            let ports: Vec<String> = svc.ports.iter().map(|p| p.to_string()).collect();
+            details.push(format!("port {}", ports.join(", ")));
+        }
+        if let Some(ref health) = svc.health {
+            details.push(health.clone());
+        }
+        if let (false, Some(code)) = (svc.state.is_running(), svc.exit_code) {
+            details.push(format!("exit {}", code));
+        }
+        if svc.state.is_running() && !svc.uptime.is_empty() {
+            details.push(svc.uptime.clone());
+        }
+        let detail_info = if details.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", details.join(", ")).dimmed().to_string()
+        };
+
+        println!(
+            "  {} {:<width$} {}{}",
+            icon,
+            svc.name,
+            svc.state,
+            detail_info,
+            width = width
+        );
+        if svc.state.is_running() {
+            for url in svc.urls() {
+                println!("      → {}", url.cyan().underline());
+            }
+        }
+    }
+}
+
+/// Display every compose service across all projects as one table
+pub fn display_ps(
+    statuses: &[(&Project, Vec<ServiceStatus>)],
+    stats: &HashMap<String, ContainerStats>,
+) {
+    let rows: Vec<PsRow> = statuses
+        .iter()
+        .flat_map(|(project, svcs)| {
+            svcs.iter().map(move |svc| {
+                let usage = stats.get(&svc.container);
+                let ports: Vec<String> = svc
+                    .ports
+                    .iter()
+                    .filter(|p| p.host_port.is_some())
+                    .map(|p| p.to_string())
+                    .collect();
+                PsRow {
+                    project: project.name.clone(),
+                    service: svc.name.clone(),
+                    state: svc.state.to_string(),
+                    ports: ports.join(", "),
+                    uptime: svc.uptime.clone(),
+                    cpu: usage.map_or("-".to_string(), |u| u.cpu.clone()),
+                    memory: usage.map_or("-".to_string(), |u| u.memory.clone()),
+                }
+            })
+        })
+        .collect();
+
+    if rows.is_empty() {
+        println!("{}", "No services running.".yellow());
+        return;
+    }
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+
+    let running = statuses
+        .iter()
+        .flat_map(|(_, svcs)| svcs.iter())
+        .filter(|s| s.state.is_running())
+        .count();
+    let active = statuses
+        .iter()
+        .filter(|(_, svcs)| svcs.iter().any(|s| s.state.is_running()))
+        .count();
+    println!(
+        "\nRunning: {} services across {} projects",
+        running.to_string().bold(),
+        active.to_string().bold()
+    );
+}
+
+/// Display the per-project summary of a batch run
+pub fn display_batch_summary(results: &[BatchResult]) {
+    let rows: Vec<BatchRow> = results
+        .iter()
+        .map(|r| BatchRow {
+            project: r.project.clone(),
+            exit: match (r.exit_code, &r.error) {
+                (Some(0), _) => "0".green().to_string(),
+                (Some(code), _) => code.to_string().red().to_string(),
+                (None, Some(e)) => e.red().to_string(),
+                (None, None) => "-".to_string(),
+            },
+            duration: format!("{:.1}s", r.duration.as_secs_f32()),
+            log: r.log.display().to_string(),
+        })
+        .collect();
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("\n{}", table);
+
+    let failed = results.iter().filter(|r| !r.success()).count();
+    println!(
+        "\n{} succeeded, {} failed",
+        (results.len() - failed).to_string().green().bold(),
+        failed.to_string().red().bold()
+    );
+}
+
+/// Display the detection rules that fully or partly matched a directory
+pub fn display_detection(matches: &[RuleMatch]) {
+    let mut selected = false;
+    let rows: Vec<DetectionRow> = matches
+        .iter()
+        .filter(|m| m.matched || !m.reasons.is_empty())
+        .map(|m| {
+            let status = if m.matched && !selected {
+                selected = true;
+                "✓".green().to_string()
+            } else if m.matched {
+                "·".to_string()
+            } else {
+                "✗".dimmed().to_string()
+            };
+            DetectionRow {
+                status,
+                project_type: m.detector.project_type.clone(),
+                stack: m.detector.stack_name().to_string(),
+                priority: m.detector.priority,
+                reasons: m.reasons.join(", "),
+                source: m.detector.source.clone(),
+            }
+        })
+        .collect();
+
+    if rows.is_empty() {
+        println!("{}", "No detection rule matched.".yellow());
+        return;
+    }
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+    println!(
+        "{}",
+        "✓ selected  · also matched  ✗ partly matched".dimmed()
+    );
+}
+
+/// Display the outcome of a git operation per project
+pub fn display_git_results(results: &[(String, Result<String, String>)]) {
+    let rows: Vec<GitResultRow> = results
+        .iter()
+        .map(|(project, result)| GitResultRow {
+            project: project.clone(),
+            result: match result {
+                Ok(msg) if msg.starts_with("skipped") => format!("{} {}", "⚠".yellow(), msg),
+                Ok(msg) => format!("{} {}", "✓".green(), msg),
+                Err(e) => format!("{} {}", "✗".red(), e),
+            },
+        })
+        .collect();
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+}
+
+/// Display local branches of each project
+pub fn display_branches(branches: &[(String, Vec<BranchInfo>)]) {
+    let rows: Vec<BranchRow> = branches
+        .iter()
+        .flat_map(|(project, list)| {
+            list.iter().map(move |b| {
+                let marker = if b.is_head { "* " } else { "  " };
+                BranchRow {
+                    project: project.clone(),
+                    branch: format!("{}{}", marker, b.name),
+                    last_commit: time_ago(b.last_commit),
+                    merged: if b.is_default {
+                        "default".cyan().to_string()
+                    } else if b.merged {
+                        "merged".green().to_string()
+                    } else {
+                        "unmerged".yellow().to_string()
+                    },
+                    upstream: b.upstream.clone().unwrap_or_else(|| "-".to_string()),
+                }
+            })
+        })
+        .collect();
+
+    if rows.is_empty() {
+        println!("{}", "No branches found.".yellow());
+        return;
+    }
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+}
+
+/// Display the projects matching a `switch` query, best first
+pub fn display_switch_matches(query: &str, matches: &[(MatchKind, &Frecency)]) {
+    if matches.is_empty() {
+        println!("{}", format!("No project matches '{}'.", query).yellow());
+        return;
+    }
+
+    let rows: Vec<SwitchMatchRow> = matches
+        .iter()
+        .enumerate()
+        .map(|(i, (kind, f))| SwitchMatchRow {
+            project: if i == 0 {
+                format!("{} {}", "→".green(), f.project.cyan().bold())
+            } else {
+                format!("  {}", f.project)
+            },
+            kind: kind.label().to_string(),
+            score: format!("{:.2}", f.score),
+            breakdown: f.breakdown(),
+            last: f.last.map_or_else(|| "never".to_string(), time_ago),
+        })
+        .collect();
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+    if matches.len() > 1 && matches[0].0 != MatchKind::Exact {
+        println!(
+            "\n{}",
+            "Several projects match; the highest frecency score wins.".dimmed()
+        );
+    }
+}
+
+/// Display registered worktrees with their git status (None: directory
+/// missing or not readable)
+pub fn display_worktrees(worktrees: &[Project], status: &[Option<GitInfo>]) {
+    if worktrees.is_empty() {
+        println!("{}", "No worktrees registered. Create one with 'projectctl worktree add'.".yellow());
+        return;
+    }
+
+    let rows: Vec<WorktreeRow> = worktrees
+        .iter()
+        .zip(status)
+        .map(|(wt, git)| WorktreeRow {
+            name: wt.name.clone(),
+            path: wt.path.clone(),
+            git: match git {
+                Some(info) => info.summary(),
+                None if !wt.exists() => format!("{} directory missing", "✗".red()),
+                None => "-".to_string(),
+            },
+        })
+        .collect();
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+}
+
+/// Display the activity report as a table, Markdown (`md`) or CSV (`csv`)
+pub fn display_report(reports: &[ProjectReport], since: DateTime<Utc>, format: &str) {
+    let since_label = since.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+
+    if format == "csv" {
+        println!("project,commits,lines_added,lines_removed,active_days,runs,run_seconds,last_used");
+        for r in reports {
+            println!(
+                "{},{},{},{},{},{},{},{}",
+                csv_field(&r.project),
+                r.commits,
+                r.insertions,
+                r.deletions,
+                r.active_days.len(),
+                r.runs,
+                r.run_ms / 1000,
+                r.last_used.map(|t| t.to_rfc3339()).unwrap_or_default()
+            );
+        }
+        return;
+    }
+
+    if reports.is_empty() {
+        println!("{}", format!("No activity since {}.", since_label).yellow());
+        return;
+    }
+
+    let total = report::total(reports);
+    let rows: Vec<ReportRow> = reports
+        .iter()
+        .chain(std::iter::once(&total))
+        .map(|r| ReportRow {
+            project: r.project.clone(),
+            commits: r.commits,
+            lines: format!("+{} -{}", r.insertions, r.deletions),
+            active_days: r.active_days.len(),
+            runs: r.runs,
+            run_time: format_duration_ms(r.run_ms),
+            last_used: match r.last_used {
+                Some(t) if format == "md" => t.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string(),
+                Some(t) => time_ago(t),
+                None => "never".to_string(),
+            },
+        })
+        .collect();
+
+    if format == "md" {
+        println!("## Activity since {}\n", since_label);
+        println!("{}", Table::new(&rows).with(Style::markdown()));
+    } else {
+        println!("{}\n", format!("Activity since {}", since_label).bold());
+        println!("{}", Table::new(&rows).with(Style::rounded()));
+    }
+}
+
+/// Quote a CSV field if it needs it
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Display what `git prune` would remove from each project
+pub fn display_prune_candidates(candidates: &[(String, Vec<PruneCandidate>)]) {
+    let rows: Vec<PruneRow> = candidates
+        .iter()
+        .flat_map(|(project, list)| {
+            list.iter().map(move |c| {
+                let details = match c {
+                    PruneCandidate::MergedBranch(b) => {
+                        format!("last commit {}", time_ago(b.last_commit))
+                    }
+                    PruneCandidate::StaleBranch(b) => format!(
+                        "{}, last commit {}",
+                        "unmerged".yellow(),
+                        time_ago(b.last_commit)
+                    ),
+                    PruneCandidate::MissingWorktree { path, .. } => format!("{} is gone", path),
+                };
+                PruneRow {
+                    project: project.clone(),
+                    kind: c.kind().to_string(),
+                    name: c.name().to_string(),
+                    details,
+                }
+            })
+        })
+        .collect();
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+}
+
+/// Display recorded runs, newest last
+pub fn display_history(records: &[RunRecord]) {
+    if records.is_empty() {
+        println!("{}", "No recorded runs yet.".yellow());
+        return;
+    }
+
+    let rows: Vec<HistoryRow> = records
+        .iter()
+        .map(|r| {
+            let mut command = r.command.clone();
+            if !r.args.is_empty() {
+                command = format!("{} -- {}", command, r.args.join(" "));
+            }
+            HistoryRow {
+                when: r
+                    .started
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+                project: r.project.clone(),
+                command,
+                duration: format_duration_ms(r.duration_ms),
+                exit: match r.exit_code {
+                    Some(0) => "0".green().to_string(),
+                    Some(code) => code.to_string().red().to_string(),
+                    None => "error".red().to_string(),
+                },
+            }
+        })
+        .collect();
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+}
+
+/// Display per-command timing statistics
+pub fn display_stats(stats: &[CommandStats]) {
+    if stats.is_empty() {
+        println!("{}", "No recorded runs yet.".yellow());
+        return;
+    }
+
+    let rows: Vec<StatsRow> = stats
+        .iter()
+        .map(|s| StatsRow {
+            project: s.project.clone(),
+            command: s.command.clone(),
+            runs: s.runs,
+            avg: format_duration_ms(s.avg_ms),
+            max: format_duration_ms(s.max_ms),
+            failures: if s.failures == 0 {
+                "0".to_string()
+            } else {
+                format!("{} ({}%)", s.failures, s.failures * 100 / s.runs)
+                    .red()
+                    .to_string()
+            },
+            last_run: s
+                .last_run
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        })
+        .collect();
+
+    let table = Table::new(&rows).with(Style::rounded()).to_string();
+    println!("{}", table);
+}
+
+/// Human-readable age of a timestamp: `5 min ago`, `2 days ago`
+pub fn time_ago(dt: DateTime<Utc>) -> String {
+    let duration = Utc::now().signed_duration_since(dt);
+    if duration.num_minutes() < 1 {
+        "just now".to_string()
+    } else if duration.num_minutes() < 60 {
+        format!("{} min ago", duration.num_minutes())
+    } else if duration.num_hours() < 24 {
+        let h = duration.num_hours();
+        format!("{} hour{} ago", h, if h == 1 { "" } else { "s" })
+    } else if duration.num_days() < 7 {
+        let d = duration.num_days();
+        format!("{} day{} ago", d, if d == 1 { "" } else { "s" })
+    } else if duration.num_weeks() < 4 {
+        let w = duration.num_weeks();
+        format!("{} week{} ago", w, if w == 1 { "" } else { "s" })
+    } else {
+        let m = (duration.num_days() / 30).max(1);
+        format!("{} month{} ago", m, if m == 1 { "" } else { "s" })
+    }
+}
+
+/// Human-readable duration: `850ms`, `12.3s` or `3m 05s`
+pub fn format_duration_ms(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}m {:02}s", ms / 60_000, (ms % 60_000) / 1000)
+    }
+}
+
 /// Get a status string for a project
-fn get_project_status(project: &Project) -> String {
+fn get_project_status(project: &Project, cache: &ProbeCache) -> String {
     if !project.exists() {
         return format!("{} Missing", "!".yellow());
     }
-    if project.has_docker_compose() && is_running(project) {
+    if cache.is_running(project) {
         return format!("{} Running", "✓".green());
     }
     "Idle".dimmed().to_string()
 }
 
-/// Check if any docker compose services are running for the project
-fn is_running(project: &Project) -> bool {
-    if !project.has_docker_compose() {
+/// Ask a yes/no question on the terminal, defaulting to no
+pub fn confirm(question: &str) -> bool {
+    print!("{} [y/N] ", question);
+    std::io::stdout().flush().ok();
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
         return false;
     }
-    let path = project.expanded_path();
-    if let Ok(svcs) = services::get_compose_status(&path) {
-        return svcs.iter().any(|(_, state, _)| state == "running");
-    }
-    false
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
 }
 
 fn capitalize(s: &str) -> String {
@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::project::{CommandDef, Project};
+
+/// Parse a dotenv file: `KEY=value` lines, optional `export` prefix,
+/// `#` comments and single or double quoted values
+pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    let mut vars = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let inner = &value[1..];
+                inner.find(quote).map_or(inner, |end| &inner[..end])
+            }
+            // Unquoted values may carry a trailing comment
+            _ => value.split(" #").next().unwrap_or(value).trim_end(),
+        };
+        if !key.is_empty() {
+            vars.push((key.to_string(), value.to_string()));
+        }
+    }
+    vars
+}
+
+fn load_dotenv(path: &Path, vars: &mut HashMap<String, String>) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read env file: {}", path.display()))?;
+    vars.extend(parse_dotenv(&content));
+    Ok(())
+}
+
+/// Execution context of a project command: working directory and the
+/// variables to set on top of the inherited environment
+#[derive(Debug)]
+pub struct CommandEnv {
+    pub cwd: PathBuf,
+    pub vars: HashMap<String, String>,
+}
+
+impl CommandEnv {
+    /// Build the environment for a command (or for hooks when `def` is None).
+    ///
+    /// Later sources win: the project's `.env`, the command's `env_file`,
+    /// `Project::env`, then the command's own `env`. The venv `bin` and
+    /// `node_modules/.bin` directories are prepended to PATH.
+    pub fn build(project: &Project, def: Option<&CommandDef>) -> Result<Self> {
+        let root = project.expanded_path();
+        let cwd = match def.and_then(|d| d.cwd.as_deref()) {
+            Some(dir) => root.join(dir),
+            None => root.clone(),
+        };
+
+        let mut vars = HashMap::new();
+        let dotenv = root.join(".env");
+        if dotenv.is_file() {
+            load_dotenv(&dotenv, &mut vars)?;
+        }
+        if let Some(env_file) = def.and_then(|d| d.env_file.as_deref()) {
+            load_dotenv(&root.join(env_file), &mut vars)?;
+        }
+        vars.extend(project.env.clone());
+        if let Some(def) = def {
+            vars.extend(def.env.clone());
+        }
+
+        let mut bin_dirs = Vec::new();
+        if let Some(venv) = project.venv_path() {
+            bin_dirs.push(venv.join("bin"));
+            vars.insert(
+                "VIRTUAL_ENV".to_string(),
+                venv.to_string_lossy().to_string(),
+            );
+        }
+        for dir in [&cwd, &root] {
+            let node_bin = dir.join("node_modules").join(".bin");
+            if node_bin.is_dir() && !bin_dirs.contains(&node_bin) {
+                bin_dirs.push(node_bin);
+            }
+        }
+        if !bin_dirs.is_empty() {
+            let base = vars
+                .get("PATH")
+                .cloned()
+                .or_else(|| std::env::var("PATH").ok())
+                .unwrap_or_default();
+            bin_dirs.extend(std::env::split_paths(&base));
+            if let Ok(path) = std::env::join_paths(bin_dirs) {
+                vars.insert("PATH".to_string(), path.to_string_lossy().to_string());
+            }
+        }
+
+        Ok(Self { cwd, vars })
+    }
+
+    /// Programs started by `script` (the first word of each `&&`, `||`,
+    /// `;` or `|` segment) that cannot be found on this environment's PATH
+    /// or relative to its working directory. Shell builtins and programs
+    /// named by a variable are not checked.
+    pub fn missing_programs(&self, script: &str) -> Vec<String> {
+        let path = self
+            .vars
+            .get("PATH")
+            .cloned()
+            .or_else(|| std::env::var("PATH").ok());
+        let script = script.replace("&&", ";").replace("||", ";");
+        script
+            .split([';', '|'])
+            .filter_map(command_word)
+            .filter(|program| !SHELL_BUILTINS.contains(program) && !program.contains('$'))
+            .filter(|program| which::which_in(program, path.as_ref(), &self.cwd).is_err())
+            .map(String::from)
+            .collect()
+    }
+
+    /// A `sh -c` command running `script` in this environment
+    pub fn shell(&self, script: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", script])
+            .current_dir(&self.cwd)
+            .envs(&self.vars);
+        cmd
+    }
+}
+
+/// Builtins commonly found at the start of command scripts
+const SHELL_BUILTINS: &[&str] = &[
+    ".", ":", "[", "alias", "cd", "echo", "eval", "exec", "exit", "export", "false", "printf",
+    "read", "set", "shift", "source", "test", "true", "unset", "wait",
+];
+
+/// Reserved words and grouping tokens that may precede a command
+const SHELL_KEYWORDS: &[&str] = &[
+    "!", "if", "then", "else", "elif", "fi", "do", "done", "while", "until", "esac", "}",
+];
+
+/// The program a segment of a script runs, skipping reserved words,
+/// subshell and group brackets and `NAME=value` prefixes. Headers of
+/// `for`, `case` and `select` run nothing.
+fn command_word(segment: &str) -> Option<&str> {
+    let mut words = segment
+        .split_whitespace()
+        .map(|w| w.trim_start_matches(['(', '{']).trim_end_matches(')'))
+        .filter(|w| !w.is_empty());
+    loop {
+        let word = words.next()?;
+        if matches!(word, "for" | "case" | "select") {
+            return None;
+        }
+        if !SHELL_KEYWORDS.contains(&word) && !is_assignment(word) {
+            return Some(word);
+        }
+    }
+}
+
+/// Whether a word is a `NAME=value` prefix of a command
+fn is_assignment(word: &str) -> bool {
+    match word.split_once('=') {
+        Some((name, _)) => {
+            !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
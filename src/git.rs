@@ -1,15 +1,31 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// Git information for a project
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitInfo {
     pub branch: String,
+    /// Upstream of the branch, e.g. `origin/main` (None if it has none)
+    #[serde(default)]
+    pub upstream: Option<String>,
     pub changed_files: usize,
     pub staged_files: usize,
     pub untracked_files: usize,
-    pub unpushed_commits: usize,
+    /// Commits not on the upstream yet
+    #[serde(default, alias = "unpushed_commits")]
+    pub ahead: usize,
+    /// Upstream commits not merged into the branch yet
+    #[serde(default)]
+    pub behind: usize,
+    #[serde(default)]
+    pub stash_count: usize,
+    /// Operation in progress: merging, rebasing, bisecting, ...
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub conflicted: Vec<String>,
     pub last_commit_message: String,
     pub last_commit_time: String,
     pub is_clean: bool,
@@ -22,17 +38,29 @@ impl GitInfo {
             .context("Not a git repository")?;
 
         let branch = get_branch_name(&repo)?;
-        let (changed_files, staged_files, untracked_files) = get_status_counts(&repo)?;
-        let is_clean = changed_files == 0 && staged_files == 0 && untracked_files == 0;
+        let status = get_status(&repo)?;
+        let is_clean = status.changed == 0
+            && status.staged == 0
+            && status.untracked == 0
+            && status.conflicted.is_empty();
         let (last_commit_message, last_commit_time) = get_last_commit(&repo)?;
-        let unpushed_commits = count_unpushed(&repo, &branch);
+        let upstream = upstream_name(&repo, &branch);
+        let (ahead, behind) = upstream
+            .as_deref()
+            .map_or((0, 0), |u| ahead_behind(&repo, &branch, u));
+        let stash_count = repo.reflog("refs/stash").map_or(0, |log| log.len());
 
         Ok(Self {
             branch,
-            changed_files,
-            staged_files,
-            untracked_files,
-            unpushed_commits,
+            upstream,
+            changed_files: status.changed,
+            staged_files: status.staged,
+            untracked_files: status.untracked,
+            ahead,
+            behind,
+            stash_count,
+            state: state_name(repo.state()).map(String::from),
+            conflicted: status.conflicted,
             last_commit_message,
             last_commit_time,
             is_clean,
@@ -54,19 +82,72 @@ impl GitInfo {
             if self.untracked_files > 0 {
                 parts.push(format!("{} untracked", self.untracked_files));
             }
+            if !self.conflicted.is_empty() {
+                parts.push(format!("{} conflicted", self.conflicted.len()));
+            }
             parts.join(", ").yellow().to_string()
         }
     }
 
+    /// Number of files with uncommitted changes of any kind
+    pub fn dirty_count(&self) -> usize {
+        self.changed_files + self.staged_files + self.untracked_files + self.conflicted.len()
+    }
+
+    /// Whether the branch has commits that exist nowhere else: ahead of its
+    /// upstream, or without an upstream at all
+    pub fn has_unpushed(&self) -> bool {
+        self.ahead > 0 || (self.upstream.is_none() && !self.branch.starts_with("detached@"))
+    }
+
+    /// Position relative to the upstream: `↑2 ↓1`, `up to date` or `no upstream`
+    pub fn sync_string(&self) -> String {
+        if self.upstream.is_none() {
+            return "no upstream".dimmed().to_string();
+        }
+        match (self.ahead, self.behind) {
+            (0, 0) => "up to date".green().to_string(),
+            (ahead, 0) => format!("↑{}", ahead).yellow().to_string(),
+            (0, behind) => format!("↓{}", behind).yellow().to_string(),
+            (ahead, behind) => format!("↑{} ↓{}", ahead, behind).red().to_string(),
+        }
+    }
+
+    /// One-line summary: status, upstream position and any operation in progress
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{}, {}", self.status_string(), self.sync_string());
+        if let Some(ref state) = self.state {
+            summary.push_str(&format!(", {}", state.to_uppercase().red().bold()));
+        }
+        if self.stash_count > 0 {
+            summary.push_str(&format!(", {} stashed", self.stash_count));
+        }
+        summary
+    }
+
     /// Display git info block
     pub fn display(&self) {
-        println!("  Branch:        {}", self.branch.cyan());
+        match self.upstream {
+            Some(ref upstream) => println!(
+                "  Branch:        {} → {}",
+                self.branch.cyan(),
+                upstream.dimmed()
+            ),
+            None => println!("  Branch:        {}", self.branch.cyan()),
+        }
         println!("  Status:        {}", self.status_string());
-        if self.unpushed_commits > 0 {
-            println!(
-                "  Unpushed:      {}",
-                format!("{} commits", self.unpushed_commits).yellow()
-            );
+        println!("  Upstream:      {}", self.sync_string());
+        if let Some(ref state) = self.state {
+            println!("  In progress:   {}", state.to_uppercase().red().bold());
+        }
+        if !self.conflicted.is_empty() {
+            println!("  Conflicts:");
+            for path in &self.conflicted {
+                println!("    {} {}", "✗".red(), path);
+            }
+        }
+        if self.stash_count > 0 {
+            println!("  Stashes:       {}", self.stash_count);
         }
         println!(
             "  Last commit:   {} ({})",
@@ -75,6 +156,14 @@ impl GitInfo {
     }
 }
 
+/// Working tree counts and conflicted paths
+struct StatusCounts {
+    changed: usize,
+    staged: usize,
+    untracked: usize,
+    conflicted: Vec<String>,
+}
+
 fn get_branch_name(repo: &git2::Repository) -> Result<String> {
     if repo.head_detached().unwrap_or(false) {
         let head = repo.head()?;
@@ -90,39 +179,48 @@ fn get_branch_name(repo: &git2::Repository) -> Result<String> {
     Ok(name)
 }
 
-fn get_status_counts(repo: &git2::Repository) -> Result<(usize, usize, usize)> {
+fn get_status(repo: &git2::Repository) -> Result<StatusCounts> {
     let mut opts = git2::StatusOptions::new();
     opts.include_untracked(true)
         .recurse_untracked_dirs(false);
 
     let statuses = repo.statuses(Some(&mut opts))?;
 
-    let mut changed = 0;
-    let mut staged = 0;
-    let mut untracked = 0;
+    let mut counts = StatusCounts {
+        changed: 0,
+        staged: 0,
+        untracked: 0,
+        conflicted: Vec::new(),
+    };
 
     for entry in statuses.iter() {
         let s = entry.status();
+        if s.contains(git2::Status::CONFLICTED) {
+            counts
+                .conflicted
+                .push(entry.path().unwrap_or_default().to_string());
+            continue;
+        }
         if s.contains(git2::Status::WT_MODIFIED)
             || s.contains(git2::Status::WT_DELETED)
             || s.contains(git2::Status::WT_RENAMED)
             || s.contains(git2::Status::WT_TYPECHANGE)
         {
-            changed += 1;
+            counts.changed += 1;
         }
         if s.contains(git2::Status::INDEX_NEW)
             || s.contains(git2::Status::INDEX_MODIFIED)
             || s.contains(git2::Status::INDEX_DELETED)
             || s.contains(git2::Status::INDEX_RENAMED)
         {
-            staged += 1;
+            counts.staged += 1;
         }
         if s.contains(git2::Status::WT_NEW) {
-            untracked += 1;
+            counts.untracked += 1;
         }
     }
 
-    Ok((changed, staged, untracked))
+    Ok(counts)
 }
 
 fn get_last_commit(repo: &git2::Repository) -> Result<(String, String)> {
@@ -157,22 +255,503 @@ fn get_last_commit(repo: &git2::Repository) -> Result<(String, String)> {
     Ok((message, time_ago))
 }
 
-fn count_unpushed(repo: &git2::Repository, branch: &str) -> usize {
-    let remote_branch = format!("origin/{}", branch);
-    let local = match repo.revparse_single(&format!("refs/heads/{}", branch)) {
-        Ok(obj) => obj.id(),
-        Err(_) => return 0,
+/// Short name of the branch's configured upstream, e.g. `origin/main`
+fn upstream_name(repo: &git2::Repository, branch: &str) -> Option<String> {
+    let name = repo
+        .branch_upstream_name(&format!("refs/heads/{}", branch))
+        .ok()?;
+    let name = name.as_str()?;
+    Some(
+        name.strip_prefix("refs/remotes/")
+            .or_else(|| name.strip_prefix("refs/heads/"))
+            .unwrap_or(name)
+            .to_string(),
+    )
+}
+
+/// Commits the branch is ahead of and behind its upstream
+fn ahead_behind(repo: &git2::Repository, branch: &str, upstream: &str) -> (usize, usize) {
+    let local = repo.revparse_single(&format!("refs/heads/{}", branch));
+    let remote = repo
+        .revparse_single(&format!("refs/remotes/{}", upstream))
+        .or_else(|_| repo.revparse_single(&format!("refs/heads/{}", upstream)));
+    match (local, remote) {
+        (Ok(local), Ok(remote)) => repo
+            .graph_ahead_behind(local.id(), remote.id())
+            .unwrap_or((0, 0)),
+        _ => (0, 0),
+    }
+}
+
+fn state_name(state: git2::RepositoryState) -> Option<&'static str> {
+    use git2::RepositoryState as S;
+    match state {
+        S::Clean => None,
+        S::Merge => Some("merging"),
+        S::Revert | S::RevertSequence => Some("reverting"),
+        S::CherryPick | S::CherryPickSequence => Some("cherry-picking"),
+        S::Bisect => Some("bisecting"),
+        S::Rebase | S::RebaseInteractive | S::RebaseMerge => Some("rebasing"),
+        S::ApplyMailbox | S::ApplyMailboxOrRebase => Some("applying patches"),
+    }
+}
+
+/// Remote callbacks that authenticate through the ssh agent or git's
+/// credential helpers, giving up after a few rejected attempts
+fn remote_callbacks<'a>(config: &'a git2::Config) -> git2::RemoteCallbacks<'a> {
+    let mut attempts = 0;
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        attempts += 1;
+        if attempts > 3 {
+            return Err(git2::Error::from_str("authentication failed"));
+        }
+        if allowed.contains(git2::CredentialType::SSH_KEY) {
+            git2::Cred::ssh_key_from_agent(username.unwrap_or("git"))
+        } else if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+            git2::Cred::credential_helper(config, url, username)
+        } else {
+            git2::Cred::default()
+        }
+    });
+    callbacks
+}
+
+fn fetch_remote(repo: &git2::Repository, name: &str) -> Result<()> {
+    let config = repo.config()?;
+    let mut remote = repo.find_remote(name)?;
+    let mut opts = git2::FetchOptions::new();
+    opts.remote_callbacks(remote_callbacks(&config))
+        .download_tags(git2::AutotagOption::Auto);
+    remote
+        .fetch(&[] as &[&str], Some(&mut opts), None)
+        .with_context(|| format!("Failed to fetch '{}'", name))?;
+    Ok(())
+}
+
+/// Fetch every remote of the repository. Returns the number of remotes.
+pub fn fetch_all(path: &Path) -> Result<usize> {
+    let repo = git2::Repository::open(path).context("Not a git repository")?;
+    let remotes = repo.remotes()?;
+    let names: Vec<&str> = remotes.iter().flatten().collect();
+    for name in &names {
+        fetch_remote(&repo, name)?;
+    }
+    Ok(names.len())
+}
+
+/// What a fast-forward pull did
+pub enum PullOutcome {
+    UpToDate,
+    /// Local commits only; nothing to pull
+    Ahead(usize),
+    FastForwarded(usize),
+    /// Working tree has uncommitted changes
+    SkippedDirty,
+    NoUpstream,
+}
+
+impl std::fmt::Display for PullOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UpToDate => write!(f, "up to date"),
+            Self::Ahead(n) => write!(f, "up to date ({} to push)", n),
+            Self::FastForwarded(n) => {
+                write!(f, "fast-forwarded {} commit{}", n, if *n == 1 { "" } else { "s" })
+            }
+            Self::SkippedDirty => write!(f, "skipped: uncommitted changes"),
+            Self::NoUpstream => write!(f, "skipped: no upstream"),
+        }
+    }
+}
+
+/// Fetch the current branch's upstream and fast-forward to it. Dirty
+/// trees are left alone; diverged branches are an error.
+pub fn pull_ff_only(path: &Path) -> Result<PullOutcome> {
+    let repo = git2::Repository::open(path).context("Not a git repository")?;
+    let status = get_status(&repo)?;
+    if status.changed > 0 || status.staged > 0 || !status.conflicted.is_empty() {
+        return Ok(PullOutcome::SkippedDirty);
+    }
+
+    let head = repo.head().context("Failed to get HEAD")?;
+    if !head.is_branch() {
+        bail!("HEAD is detached");
+    }
+    let refname = head.name().context("Invalid branch name")?.to_string();
+    let Ok(remote) = repo.branch_upstream_remote(&refname) else {
+        return Ok(PullOutcome::NoUpstream);
+    };
+    let remote = remote.as_str().context("Invalid remote name")?.to_string();
+    let upstream_ref = repo.branch_upstream_name(&refname)?;
+    let upstream_ref = upstream_ref
+        .as_str()
+        .context("Invalid upstream name")?
+        .to_string();
+
+    // A local branch as upstream ("." remote) needs no fetch
+    if remote != "." {
+        fetch_remote(&repo, &remote)?;
+    }
+
+    let local = head.target().context("Branch has no commits")?;
+    let upstream = repo.refname_to_id(&upstream_ref)?;
+    let (ahead, behind) = repo.graph_ahead_behind(local, upstream)?;
+    match (ahead, behind) {
+        (0, 0) => return Ok(PullOutcome::UpToDate),
+        (ahead, 0) => return Ok(PullOutcome::Ahead(ahead)),
+        (0, _) => {}
+        (ahead, behind) => bail!(
+            "diverged from upstream ({} ahead, {} behind); merge or rebase manually",
+            ahead,
+            behind
+        ),
+    }
+
+    // Update the working tree first: a safe checkout refuses to overwrite
+    // untracked files the incoming commits would replace
+    let target = repo.find_commit(upstream)?;
+    repo.checkout_tree(
+        target.as_object(),
+        Some(git2::build::CheckoutBuilder::new().safe()),
+    )
+    .context("Checkout would overwrite local files")?;
+    repo.find_reference(&refname)?.set_target(
+        upstream,
+        &format!("projectctl: fast-forward to {}", upstream_ref),
+    )?;
+    Ok(PullOutcome::FastForwarded(behind))
+}
+
+/// A local branch with its relation to the default branch
+#[derive(Debug, Clone)]
+pub struct BranchInfo {
+    pub name: String,
+    pub is_head: bool,
+    pub is_default: bool,
+    /// Merged into the default branch
+    pub merged: bool,
+    pub last_commit: chrono::DateTime<chrono::Utc>,
+    pub upstream: Option<String>,
+}
+
+/// The repository's default branch: the target of `<remote>/HEAD` if
+/// known, otherwise a local `main` or `master`
+pub fn default_branch(repo: &git2::Repository) -> Option<String> {
+    for remote in repo.remotes().ok()?.iter().flatten() {
+        if let Ok(head) = repo.find_reference(&format!("refs/remotes/{}/HEAD", remote)) {
+            if let Some(target) = head.symbolic_target() {
+                let prefix = format!("refs/remotes/{}/", remote);
+                if let Some(name) = target.strip_prefix(&prefix) {
+                    return Some(name.to_string());
+                }
+            }
+        }
+    }
+    ["main", "master"]
+        .iter()
+        .find(|b| repo.find_branch(b, git2::BranchType::Local).is_ok())
+        .map(|b| b.to_string())
+}
+
+/// Local branches, most recently committed first
+pub fn list_branches(path: &Path) -> Result<Vec<BranchInfo>> {
+    let repo = git2::Repository::open(path).context("Not a git repository")?;
+    let default = default_branch(&repo);
+    let default_oid = default.as_ref().and_then(|name| {
+        repo.find_branch(name, git2::BranchType::Local)
+            .ok()
+            .and_then(|b| b.get().target())
+    });
+
+    let mut branches = Vec::new();
+    for entry in repo.branches(Some(git2::BranchType::Local))? {
+        let (branch, _) = entry?;
+        let Some(name) = branch.name()?.map(String::from) else {
+            continue;
+        };
+        let Some(oid) = branch.get().target() else {
+            continue;
+        };
+        let commit = repo.find_commit(oid)?;
+        let is_default = default.as_deref() == Some(name.as_str());
+        let merged = match default_oid {
+            Some(base) => oid == base || repo.graph_descendant_of(base, oid).unwrap_or(false),
+            None => false,
+        };
+        let upstream = branch
+            .upstream()
+            .ok()
+            .and_then(|u| u.name().ok().flatten().map(String::from));
+        branches.push(BranchInfo {
+            is_head: branch.is_head(),
+            is_default,
+            merged,
+            last_commit: chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+                .unwrap_or_default(),
+            upstream,
+            name,
+        });
+    }
+    branches.sort_by_key(|b| std::cmp::Reverse(b.last_commit));
+    Ok(branches)
+}
+
+/// Something `git prune` would remove from a repository
+#[derive(Debug, Clone)]
+pub enum PruneCandidate {
+    /// Local branch already merged into the default branch
+    MergedBranch(BranchInfo),
+    /// Unmerged local branch without commits for a while
+    StaleBranch(BranchInfo),
+    /// Registered worktree whose directory no longer exists
+    MissingWorktree { name: String, path: String },
+}
+
+impl PruneCandidate {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PruneCandidate::MergedBranch(_) => "merged branch",
+            PruneCandidate::StaleBranch(_) => "stale branch",
+            PruneCandidate::MissingWorktree { .. } => "missing worktree",
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            PruneCandidate::MergedBranch(b) | PruneCandidate::StaleBranch(b) => &b.name,
+            PruneCandidate::MissingWorktree { name, .. } => name,
+        }
+    }
+}
+
+/// Branches checked out in linked worktrees (they cannot be deleted).
+/// The head is read from the worktree's gitdir, so worktrees whose
+/// directory is gone still count.
+fn worktree_heads(repo: &git2::Repository) -> Vec<String> {
+    let Ok(names) = repo.worktrees() else {
+        return Vec::new();
+    };
+    names
+        .iter()
+        .flatten()
+        .filter_map(|name| {
+            let gitdir = repo.path().join("worktrees").join(name);
+            std::fs::read_to_string(gitdir.join("HEAD"))
+                .ok()?
+                .trim()
+                .strip_prefix("ref: refs/heads/")
+                .map(String::from)
+        })
+        .collect()
+}
+
+/// Branches merged into the default branch or untouched for `stale_days`,
+/// and worktrees whose directories are gone. The current branch, the
+/// default branch and branches checked out in a worktree are never listed.
+pub fn prune_candidates(path: &Path, stale_days: i64) -> Result<Vec<PruneCandidate>> {
+    let repo = git2::Repository::open(path).context("Not a git repository")?;
+    let checked_out = worktree_heads(&repo);
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(stale_days);
+
+    let mut candidates = Vec::new();
+    for branch in list_branches(path)? {
+        if branch.is_head || branch.is_default || checked_out.contains(&branch.name) {
+            continue;
+        }
+        if branch.merged {
+            candidates.push(PruneCandidate::MergedBranch(branch));
+        } else if branch.last_commit < cutoff {
+            candidates.push(PruneCandidate::StaleBranch(branch));
+        }
+    }
+
+    for name in repo.worktrees()?.iter().flatten() {
+        let wt = repo.find_worktree(name)?;
+        let locked = !matches!(wt.is_locked(), Ok(git2::WorktreeLockStatus::Unlocked));
+        if !locked && !wt.path().exists() {
+            candidates.push(PruneCandidate::MissingWorktree {
+                name: name.to_string(),
+                path: wt.path().display().to_string(),
+            });
+        }
+    }
+    Ok(candidates)
+}
+
+/// Prune the given worktrees, then delete the given branches.
+/// Returns how many branches and worktrees were removed.
+pub fn prune(path: &Path, candidates: &[PruneCandidate]) -> Result<(usize, usize)> {
+    let repo = git2::Repository::open(path).context("Not a git repository")?;
+    let (mut branches, mut worktrees) = (0, 0);
+    // Worktrees first: a branch still checked out in one cannot be deleted
+    let (missing, branch_candidates): (Vec<&PruneCandidate>, Vec<&PruneCandidate>) = candidates
+        .iter()
+        .partition(|c| matches!(c, PruneCandidate::MissingWorktree { .. }));
+    for candidate in missing.into_iter().chain(branch_candidates) {
+        match candidate {
+            PruneCandidate::MergedBranch(b) | PruneCandidate::StaleBranch(b) => {
+                repo.find_branch(&b.name, git2::BranchType::Local)?
+                    .delete()
+                    .with_context(|| format!("Failed to delete branch {}", b.name))?;
+                branches += 1;
+            }
+            PruneCandidate::MissingWorktree { name, .. } => {
+                repo.find_worktree(name)?
+                    .prune(None)
+                    .with_context(|| format!("Failed to prune worktree {}", name))?;
+                worktrees += 1;
+            }
+        }
+    }
+    Ok((branches, worktrees))
+}
+
+/// Check out `branch` into a new worktree at `dest`. An existing local
+/// branch is used as is; otherwise the branch is created from the remote
+/// branch of the same name (tracking it) or from HEAD. Returns where the
+/// branch came from.
+pub fn add_worktree(path: &Path, branch: &str, dest: &Path) -> Result<String> {
+    let repo = git2::Repository::open(path).context("Not a git repository")?;
+    if dest.exists() {
+        bail!("{} already exists", dest.display());
+    }
+
+    let (reference, origin, created) = match repo.find_branch(branch, git2::BranchType::Local) {
+        Ok(local) => (local.into_reference(), "existing branch".to_string(), false),
+        Err(_) => {
+            let remote = repo
+                .branches(Some(git2::BranchType::Remote))?
+                .flatten()
+                .map(|(b, _)| b)
+                .find(|b| {
+                    b.name().ok().flatten().and_then(|n| n.split_once('/')).map(|(_, n)| n)
+                        == Some(branch)
+                });
+            let (commit, upstream) = match remote {
+                Some(remote) => {
+                    let name = remote.name()?.map(String::from);
+                    (remote.get().peel_to_commit()?, name)
+                }
+                None => (repo.head()?.peel_to_commit()?, None),
+            };
+            let mut local = repo.branch(branch, &commit, false)?;
+            let origin = match upstream {
+                Some(upstream) => {
+                    if let Err(e) = local.set_upstream(Some(&upstream)) {
+                        local.delete().ok();
+                        return Err(e).context("Failed to set the upstream branch");
+                    }
+                    format!("new branch tracking {}", upstream)
+                }
+                None => "new branch from HEAD".to_string(),
+            };
+            (local.into_reference(), origin, true)
+        }
     };
-    let remote = match repo.revparse_single(&format!("refs/remotes/{}", remote_branch)) {
-        Ok(obj) => obj.id(),
-        Err(_) => return 0,
+
+    // Worktree names live under .git/worktrees/ and cannot contain '/'
+    let name = branch.replace('/', "-");
+    let mut opts = git2::WorktreeAddOptions::new();
+    opts.reference(Some(&reference));
+    if let Err(e) = repo.worktree(&name, dest, Some(&opts)) {
+        // Do not leave behind a branch created only for this worktree
+        if created {
+            if let Ok(mut local) = repo.find_branch(branch, git2::BranchType::Local) {
+                local.delete().ok();
+            }
+        }
+        return Err(e).with_context(|| format!("Failed to create worktree for {}", branch));
+    }
+    Ok(origin)
+}
+
+/// Delete the worktree at `dest` and its git metadata. Worktrees with
+/// uncommitted changes are kept unless `force` is set.
+pub fn remove_worktree(path: &Path, dest: &Path, force: bool) -> Result<()> {
+    let repo = git2::Repository::open(path).context("Not a git repository")?;
+    let dest = dest.canonicalize().unwrap_or_else(|_| dest.to_path_buf());
+    let worktree = repo
+        .worktrees()?
+        .iter()
+        .flatten()
+        .filter_map(|name| repo.find_worktree(name).ok())
+        .find(|wt| wt.path().canonicalize().unwrap_or_else(|_| wt.path().to_path_buf()) == dest);
+    let Some(worktree) = worktree else {
+        bail!("{} is not a worktree of {}", dest.display(), path.display());
     };
 
-    let mut count = 0;
-    if let Ok(mut walk) = repo.revwalk() {
-        walk.push(local).ok();
-        walk.hide(remote).ok();
-        count = walk.count();
+    if dest.exists() && !force {
+        let status = get_status(&git2::Repository::open(&dest)?)?;
+        let dirty = status.changed + status.staged + status.untracked + status.conflicted.len();
+        if dirty > 0 {
+            bail!("Worktree has {} uncommitted change(s); use --force to delete it anyway", dirty);
+        }
+    }
+
+    let mut opts = git2::WorktreePruneOptions::new();
+    opts.valid(true).working_tree(true).locked(force);
+    worktree.prune(Some(&mut opts)).context("Failed to remove worktree")?;
+    Ok(())
+}
+
+/// Commits of one author in a repository over a period
+#[derive(Debug, Clone, Default)]
+pub struct CommitActivity {
+    pub commits: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Local dates with at least one commit
+    pub days: std::collections::BTreeSet<chrono::NaiveDate>,
+}
+
+/// The `user.email` git would use in this repository
+pub fn user_email(path: &Path) -> Option<String> {
+    let repo = git2::Repository::open(path).ok()?;
+    repo.config().ok()?.get_string("user.email").ok()
+}
+
+/// Non-merge commits by `email` on any local branch since `since`. Like
+/// `git log --since`, the walk stops at the first older commit.
+pub fn commit_activity(
+    path: &Path,
+    email: &str,
+    since: chrono::DateTime<chrono::Utc>,
+) -> Result<CommitActivity> {
+    let repo = git2::Repository::open(path).context("Not a git repository")?;
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(git2::Sort::TIME)?;
+    walk.push_glob("refs/heads")?;
+
+    let mut activity = CommitActivity::default();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        let Some(time) = chrono::DateTime::from_timestamp(commit.time().seconds(), 0) else {
+            continue;
+        };
+        if time < since {
+            break;
+        }
+        let author = commit.author();
+        let is_author = author
+            .email()
+            .is_some_and(|e| e.eq_ignore_ascii_case(email));
+        if !is_author || commit.parent_count() > 1 {
+            continue;
+        }
+
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+        let stats = diff.stats()?;
+        activity.commits += 1;
+        activity.insertions += stats.insertions();
+        activity.deletions += stats.deletions();
+        activity
+            .days
+            .insert(time.with_timezone(&chrono::Local).date_naive());
     }
-    count
+    Ok(activity)
 }
@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::time::Duration;
+
+use crate::config::ConfigManager;
+
+/// One `projectctl run` invocation, as stored in ~/.projectctl/history.jsonl
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RunRecord {
+    pub project: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    pub started: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// None when the command could not be started
+    pub exit_code: Option<i32>,
+}
+
+impl RunRecord {
+    pub fn new(project: &str, command: &str, args: &[String], started: DateTime<Utc>) -> Self {
+        Self {
+            project: project.to_string(),
+            command: command.to_string(),
+            args: args.to_vec(),
+            started,
+            duration_ms: 0,
+            exit_code: None,
+        }
+    }
+
+    /// Fill in the outcome of the run
+    pub fn finish(mut self, duration: Duration, exit_code: Option<i32>) -> Self {
+        self.duration_ms = duration.as_millis() as u64;
+        self.exit_code = exit_code;
+        self
+    }
+
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Append a run to the history file
+pub fn append(config: &ConfigManager, record: &RunRecord) -> Result<()> {
+    config.ensure_dirs()?;
+    let line = serde_json::to_string(record).context("Failed to serialize run record")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(config.history_path())
+        .context("Failed to open history.jsonl")?;
+    writeln!(file, "{}", line).context("Failed to write history.jsonl")?;
+    Ok(())
+}
+
+/// Load all recorded runs, oldest first. Unreadable lines are skipped.
+pub fn load(config: &ConfigManager) -> Result<Vec<RunRecord>> {
+    let path = config.history_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).context("Failed to read history.jsonl")?;
+    Ok(content
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+/// Aggregated timing of one project command
+#[derive(Debug)]
+pub struct CommandStats {
+    pub project: String,
+    pub command: String,
+    pub runs: usize,
+    pub failures: usize,
+    pub avg_ms: u64,
+    pub max_ms: u64,
+    pub last_run: DateTime<Utc>,
+}
+
+/// Aggregate runs per project command
+pub fn stats(records: &[RunRecord]) -> Vec<CommandStats> {
+    let mut stats: Vec<CommandStats> = Vec::new();
+    for record in records {
+        let idx = match stats
+            .iter()
+            .position(|s| s.project == record.project && s.command == record.command)
+        {
+            Some(idx) => idx,
+            None => {
+                stats.push(CommandStats {
+                    project: record.project.clone(),
+                    command: record.command.clone(),
+                    runs: 0,
+                    failures: 0,
+                    avg_ms: 0,
+                    max_ms: 0,
+                    last_run: record.started,
+                });
+                stats.len() - 1
+            }
+        };
+        let entry = &mut stats[idx];
+        // Running mean keeps this a single pass
+        let runs = entry.runs as u64;
+        entry.avg_ms = (entry.avg_ms * runs + record.duration_ms) / (runs + 1);
+        entry.runs += 1;
+        entry.max_ms = entry.max_ms.max(record.duration_ms);
+        entry.last_run = entry.last_run.max(record.started);
+        if !record.success() {
+            entry.failures += 1;
+        }
+    }
+    stats
+}
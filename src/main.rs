@@ -1,17 +1,32 @@
+mod activity;
+mod cache;
 mod config;
 mod deps;
+mod detect;
 mod display;
+mod env;
 mod git;
+mod history;
+mod probe;
 mod project;
+mod report;
+mod runner;
 mod services;
+mod tasks;
 mod templates;
+mod tui;
+mod wizard;
+mod workspace;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Parser, Subcommand};
 use colored::Colorize;
+use std::path::Path;
 use std::process::Command;
 
+use cache::StatusCache;
 use config::ConfigManager;
+use probe::ProbeCache;
 use project::Project;
 
 #[derive(Parser)]
@@ -26,7 +41,9 @@ Common workflows:
   Switch context:       projectctl switch myapp
   View all projects:    projectctl list --detailed
   Start services:       projectctl start myapp
+  What is running:      projectctl ps
   View recent:          projectctl recent
+  Pull everything:      projectctl exec --all -- git pull
   Create new project:   projectctl new myapp --template react-vite
   Shell completions:    projectctl completions zsh >> ~/.zshrc"
 )]
@@ -45,22 +62,42 @@ Shows project name, type, status, and last-used time. Use filters to narrow
 results by type or running state. The --detailed flag adds paths, commands,
 and services for each project.
 
+Status is read from the cache in ~/.projectctl/cache/ so the list renders
+instantly; entries older than a few minutes are marked with '*'. Use
+--refresh to probe docker and git again.
+
 Examples:
   projectctl list                        # Show all projects
   projectctl list --detailed             # Show with paths and commands
   projectctl list -t fastapi             # Filter by type
   projectctl list --active               # Only projects with running services
+  projectctl list --refresh              # Re-probe instead of using the cache
+  projectctl list --git                  # Add branch, dirty and ahead/behind columns
+  projectctl list --dirty --unpushed     # Anything stranded before leaving?
   projectctl list -t react --detailed    # Combine filters")]
     List {
         /// Show detailed information
         #[arg(short, long)]
         detailed: bool,
+        /// Show branch, dirty file count and ahead/behind columns
+        #[arg(short, long)]
+        git: bool,
+        /// Only projects with uncommitted changes (git is probed live)
+        #[arg(long)]
+        dirty: bool,
+        /// Only projects with commits not pushed to their upstream, or
+        /// branches without one (git is probed live)
+        #[arg(long)]
+        unpushed: bool,
         /// Filter by project type
         #[arg(short = 't', long = "type")]
         project_type: Option<String>,
         /// Show only active projects (with running services)
         #[arg(short, long)]
         active: bool,
+        /// Probe status live and update the cache
+        #[arg(short, long)]
+        refresh: bool,
     },
 
     /// Switch to a project
@@ -69,16 +106,21 @@ Switch to a project context.
 
 Displays the project directory, activates any detected Python virtualenv,
 shows git branch status, and optionally opens the project in VSCode.
-Supports fuzzy name matching (partial, prefix, or substring).
+Supports fuzzy name matching (partial, prefix, or substring). When several
+projects match, the one used most often and most recently (frecency) wins;
+--explain shows the candidates and their scores. Like 'cd -', 'switch -'
+goes back to the project switched to before this one.
 
 Examples:
   projectctl switch myapp                # Switch by name
   projectctl switch my                   # Partial name match
+  projectctl switch -                    # Back to the previous project
+  projectctl switch api --explain        # Why 'api' picks which project
   projectctl switch --recent             # Switch to last used project
   projectctl switch myapp --code         # Switch and open in VSCode
   projectctl switch uniforme -c          # Fuzzy match + VSCode")]
     Switch {
-        /// Project name (or partial match)
+        /// Project name (or partial match, or - for the previous project)
         name: Option<String>,
         /// Switch to the most recent project
         #[arg(short, long)]
@@ -86,6 +128,9 @@ Examples:
         /// Also open in VSCode
         #[arg(short, long)]
         code: bool,
+        /// Show how matching projects are ranked instead of switching
+        #[arg(short, long, conflicts_with = "recent")]
+        explain: bool,
     },
 
     /// Show project details
@@ -103,8 +148,8 @@ Examples:
   projectctl info myapp --path-only      # Print path (for scripts)
   cd $(projectctl info myapp --path-only)  # Shell integration")]
     Info {
-        /// Project name
-        name: String,
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
         /// Show git info
         #[arg(short, long)]
         git: bool,
@@ -124,17 +169,27 @@ Runs 'docker compose up -d' in the project directory. Optionally start
 only a specific service by name. The project must have a docker-compose.yml
 or compose.yml file.
 
+Once services are running (and healthy, if they define a healthcheck), the
+project's post_start hooks are run, e.g. database migrations:
+
+  [project.hooks]
+  post_start = [\"alembic upgrade head\"]
+
 Examples:
   projectctl start myapp                 # Start all services
   projectctl start myapp -s backend      # Start only backend service
   projectctl start myapp -s postgres     # Start only the database
+  projectctl start myapp --no-hooks      # Skip post_start hooks
   projectctl start uniforme --service redis  # Start Redis for a project")]
     Start {
-        /// Project name
-        name: String,
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
         /// Start only a specific service
         #[arg(short, long)]
         service: Option<String>,
+        /// Do not run post_start hooks
+        #[arg(long)]
+        no_hooks: bool,
     },
 
     /// Stop project services
@@ -142,18 +197,24 @@ Examples:
 Stop Docker Compose services for a project.
 
 Runs 'docker compose stop' (or 'docker compose stop <service>') in the
-project directory. Does not remove containers or volumes.
+project directory. Does not remove containers or volumes; use
+'projectctl services down' for that. The project's pre_stop hooks run first,
+while services are still up.
 
 Examples:
   projectctl stop myapp                  # Stop all services
   projectctl stop myapp -s backend       # Stop only backend
+  projectctl stop myapp --no-hooks       # Skip pre_stop hooks
   projectctl stop myapp --service redis  # Stop a specific service")]
     Stop {
-        /// Project name
-        name: String,
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
         /// Stop only a specific service
         #[arg(short, long)]
         service: Option<String>,
+        /// Do not run pre_stop hooks
+        #[arg(long)]
+        no_hooks: bool,
     },
 
     /// Restart project services
@@ -168,8 +229,8 @@ Examples:
   projectctl restart myapp -s backend    # Restart only backend
   projectctl restart myapp --service api # Restart a specific service")]
     Restart {
-        /// Project name
-        name: String,
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
         /// Restart only a specific service
         #[arg(short, long)]
         service: Option<String>,
@@ -190,8 +251,8 @@ Examples:
   projectctl logs myapp -l 200           # Show last 200 lines
   projectctl logs myapp -s api -l 100 -f  # Follow API with 100-line history")]
     Logs {
-        /// Project name
-        name: String,
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
         /// Show logs for a specific service
         #[arg(short, long)]
         service: Option<String>,
@@ -203,6 +264,46 @@ Examples:
         lines: usize,
     },
 
+    /// Manage service containers and their data volumes
+    #[command(long_about = "\
+Manage Docker Compose containers and the data in their named volumes.
+
+'down' removes containers and networks (and with --volumes, all data).
+'reset' recreates one service with its named volumes wiped, e.g. to get a
+fresh database. 'backup' snapshots a service's named volumes to tarballs
+under ~/.projectctl/backups/<project>/, and 'restore' loads a snapshot back.
+Destructive actions ask for confirmation unless --yes is given.
+
+Examples:
+  projectctl services down myapp             # Remove containers
+  projectctl services down myapp --volumes   # Also delete all data
+  projectctl services reset myapp postgres   # Fresh, empty database
+  projectctl services backup myapp postgres  # Snapshot the database
+  projectctl services restore myapp postgres # Restore latest snapshot
+  projectctl services restore myapp postgres --from 20240101-120000")]
+    Services {
+        #[command(subcommand)]
+        action: ServicesAction,
+    },
+
+    /// Show running services across all projects
+    #[command(long_about = "\
+Show every Docker Compose service running across all registered projects.
+
+Queries each project's compose status in parallel and prints one table with
+the project, service, state, published ports, uptime, and CPU/memory usage
+(from 'docker stats'). Use --stop-all-except to free resources by stopping
+the services of every other project.
+
+Examples:
+  projectctl ps                          # Dashboard of everything running
+  projectctl ps --stop-all-except myapp  # Stop everything but myapp")]
+    Ps {
+        /// Stop services of all projects except this one
+        #[arg(long, value_name = "PROJECT")]
+        stop_all_except: Option<String>,
+    },
+
     /// Dependency management
     #[command(long_about = "\
 Manage project dependencies across your registered projects.
@@ -227,23 +328,157 @@ Examples:
 Run a custom command defined in the project configuration.
 
 Commands are defined per-project in ~/.projectctl/projects.toml under
-[project.commands]. Use --list to see available commands for a project.
-The command is executed in the project's root directory.
+[project.commands]. Scripts and targets from package.json, Makefile,
+justfile, Taskfile.yml, pyproject.toml (poetry/pdm) and Cargo aliases are
+available too. Use --list to see available commands and where they come from.
+A command is either a string or a table with 'cmd', 'cwd' (relative to the
+project root), 'env' and 'env_file':
+
+  [project.commands]
+  test = \"pytest\"
+  web = { cmd = \"npm run dev\", cwd = \"frontend\", env_file = \".env.dev\" }
+
+The environment is built from the project's .env, the command's env_file,
+[project.env] and the command's env, with the venv bin and node_modules/.bin
+directories prepended to PATH.
+
+A table may also list 'steps' (shell strings run in order before 'cmd') and
+'depends' (other commands run first; cycles are rejected). Arguments after
+'--' are passed to the command: in place of {args} if it is used, otherwise
+appended. {project} and {project_path} are expanded as well; all values
+are shell-quoted.
+
+  build = { depends = [\"lint\"], steps = [\"npm run build\", \"npm run size\"] }
 
 Examples:
   projectctl run myapp dev               # Run the 'dev' command
   projectctl run myapp test              # Run the 'test' command
   projectctl run myapp build             # Run the 'build' command
+  projectctl run myapp test -- -k auth   # Pass extra arguments
+  projectctl run --all test              # Run 'test' in every project that has it
+  projectctl run myapp --last            # Re-run its previous command
   projectctl run myapp --list            # List available commands
-  projectctl run myapp                   # Also lists commands (no args)")]
+  projectctl run myapp                   # Also lists commands (no args)
+  projectctl run test                    # Inside a project directory")]
     Run {
-        /// Project name
-        name: String,
+        /// Project name, or a command of the current directory's project
+        /// (with --all: the command to run)
+        name: Option<String>,
         /// Command to run (e.g., dev, test, build)
         command: Option<String>,
+        /// Extra arguments passed to the command (after --)
+        #[arg(last = true)]
+        args: Vec<String>,
         /// List available commands
         #[arg(short, long)]
         list: bool,
+        /// Run the command in every project that defines it
+        #[arg(short, long, requires = "name", conflicts_with_all = ["list", "command"])]
+        all: bool,
+        /// Number of projects to run in parallel with --all
+        #[arg(short, long, default_value_t = probe::MAX_WORKERS)]
+        jobs: usize,
+        /// Re-run the previous command of the project (or of any project
+        /// outside a project directory)
+        #[arg(long, conflicts_with_all = ["command", "all", "list"])]
+        last: bool,
+    },
+
+    /// Show recent command runs
+    #[command(long_about = "\
+Show recent 'projectctl run' invocations from ~/.projectctl/history.jsonl.
+
+Every run is recorded with its project, command, start time, duration and
+exit status.
+
+Examples:
+  projectctl history                     # Last 20 runs across projects
+  projectctl history myapp               # Runs of one project
+  projectctl history -l 50               # Show more")]
+    History {
+        /// Only show runs of this project
+        project: Option<String>,
+        /// Maximum number of runs to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+
+    /// Show command timing and failure statistics
+    #[command(long_about = "\
+Aggregate recorded runs per project command: number of runs, average and
+slowest duration, and how often each command fails.
+
+Examples:
+  projectctl stats                       # Slowest commands first
+  projectctl stats --failures            # Most failing commands first
+  projectctl stats myapp                 # Only one project")]
+    Stats {
+        /// Only include runs of this project
+        project: Option<String>,
+        /// Sort by failure count instead of average duration
+        #[arg(short, long)]
+        failures: bool,
+        /// Maximum number of commands to show
+        #[arg(short, long, default_value = "15")]
+        limit: usize,
+    },
+
+    /// Summarize commits and activity per project
+    #[command(long_about = "\
+Summarize your work per project over a period, for timesheets and standups.
+
+For every registered repository, commits by your git user.email (or
+--author) on any local branch are counted with the lines they changed.
+Recorded runs, switches and starts are added, and days with any of these
+count as active days.
+
+Examples:
+  projectctl report                      # The last 7 days
+  projectctl report --since 2w           # The last two weeks
+  projectctl report --since 2024-05-01   # Since a date
+  projectctl report -f md > week.md      # Markdown for a standup note
+  projectctl report -f csv               # For a spreadsheet")]
+    Report {
+        /// Start of the period: 12h, 7d, 2w or a date (YYYY-MM-DD)
+        #[arg(short, long, default_value = "7d")]
+        since: String,
+        /// Output format
+        #[arg(short, long, default_value = "table", value_parser = ["table", "md", "csv"])]
+        format: String,
+        /// Count commits of this email instead of each repository's user.email
+        #[arg(long)]
+        author: Option<String>,
+    },
+
+    /// Run a shell command across many projects
+    #[command(long_about = "\
+Run a shell command in the root of many projects in parallel.
+
+Select projects by name, by type (--type), or all of them (--all). Each
+project's output is written to a log under ~/.projectctl/logs/, and a
+summary table of exit code, duration and log path is printed at the end.
+The command runs with the project's environment (.env, [project.env], venv).
+
+Examples:
+  projectctl exec --all -- git pull              # Pull every project
+  projectctl exec --type fastapi -- pytest -q    # All FastAPI backends
+  projectctl exec api web -- npm ci              # Selected projects
+  projectctl exec --all -j 2 -- 'make clean && make'")]
+    Exec {
+        /// Projects to run in
+        names: Vec<String>,
+        /// Run in all registered projects
+        #[arg(short, long, conflicts_with_all = ["names", "project_type"])]
+        all: bool,
+        /// Run in projects of this type
+        #[arg(short = 't', long = "type", conflicts_with = "names")]
+        project_type: Option<String>,
+        /// Number of projects to run in parallel
+        #[arg(short, long, default_value_t = probe::MAX_WORKERS)]
+        jobs: usize,
+        /// Command to run (after --)
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
     },
 
     /// Add a project
@@ -255,12 +490,18 @@ available services (from docker-compose), and common commands. If --path
 is omitted, the current directory is used. The project name defaults to
 the directory name.
 
+With --interactive, every detected setting is shown for review: keep, edit
+or remove the name, type, services and each command, and add your own.
+Commands whose program is not on PATH (including the project's venv and
+node_modules/.bin) are flagged before the project is saved.
+
 Examples:
   projectctl add --path ~/code/myapp     # Register with auto-detect
   projectctl add                         # Register current directory
   projectctl add --name api --path ~/code/backend  # Custom name
   projectctl add -p ~/code/app -t react  # Explicit type
-  projectctl add -n myproject -p . -t fastapi  # All options")]
+  projectctl add -n myproject -p . -t fastapi  # All options
+  projectctl add -i                      # Review what was detected first")]
     Add {
         /// Custom project name
         #[arg(short, long)]
@@ -271,6 +512,34 @@ Examples:
         /// Project type (auto-detected if not given)
         #[arg(short = 't', long = "type")]
         project_type: Option<String>,
+        /// Review and edit detected settings before saving
+        #[arg(short, long)]
+        interactive: bool,
+    },
+
+    /// Detect the project type of a directory
+    #[command(long_about = "\
+Detect the project type of a directory, as 'projectctl add' would.
+
+Types are detected by rules matching marker files, keys in package.json,
+pyproject.toml or Cargo.toml, and declared runtime dependencies. The
+matching rule with the highest priority wins. Add your own rules in
+~/.projectctl/detectors.toml; they take precedence on equal priority:
+
+  [[detector]]
+  type = \"qwik\"
+  priority = 60
+  dependencies = [\"@builder.io/qwik\"]
+
+Examples:
+  projectctl detect                      # Current directory
+  projectctl detect ~/code/app --explain # Show which rules matched")]
+    Detect {
+        /// Directory to inspect (default: current directory)
+        path: Option<String>,
+        /// Show the rules that matched and why
+        #[arg(short, long)]
+        explain: bool,
     },
 
     /// Remove a project from the registry
@@ -292,11 +561,12 @@ Examples:
 
     /// Show recently used projects
     #[command(long_about = "\
-Show recently used projects sorted by last access time.
+Show recently used projects ranked by frecency.
 
-Displays projects ordered by when they were last switched to. Useful for
-quickly finding the project you were working on. The --limit flag controls
-how many entries to show.
+Every switch, run and start is logged in ~/.projectctl/activity.jsonl.
+Projects are ranked by how often and how recently they were used, so the
+ones you work on daily stay on top even after a quick look elsewhere. The
+--limit flag controls how many entries to show.
 
 Examples:
   projectctl recent                      # Show last 10 projects
@@ -351,6 +621,93 @@ Examples:
         action: Option<TemplatesAction>,
     },
 
+    /// Git operations across projects
+    #[command(long_about = "\
+Run git operations across many repositories at once, in parallel.
+
+Select projects by name or with --all; without either, the project of the
+current directory is used. Results are printed as one summary table.
+
+'pull' only fast-forwards the current branch to its upstream. Projects
+with uncommitted changes are skipped, and diverged branches are reported
+instead of merged.
+
+'prune' lists local branches merged into the default branch or without
+commits for --days, and worktrees whose directories are gone, then deletes
+them after confirmation. The current and default branches are kept.
+
+Examples:
+  projectctl git fetch --all             # Fetch every remote of every project
+  projectctl git pull --ff-only --all    # Fast-forward everything that is clean
+  projectctl git branches --all          # Local branches, merged or not
+  projectctl git branches api web        # Selected projects
+  projectctl git prune --all --dry-run   # What could be cleaned up
+  projectctl git prune api --days 30 -y  # Delete without asking")]
+    Git {
+        #[command(subcommand)]
+        action: GitAction,
+    },
+
+    /// Manage git worktrees of a project
+    #[command(long_about = "\
+Check out other branches of a project as git worktrees.
+
+A worktree is registered as part of its project and addressed as
+project@branch. It inherits the project's commands, env and hooks, and
+works with switch, run, start and every other command taking a name.
+New worktrees are created next to the project directory as <dir>-<branch>
+unless --path is given.
+
+Examples:
+  projectctl worktree add api feature/login     # Check out a branch next to api/
+  projectctl switch api@feature/login           # Jump into it
+  projectctl run api@feature/login test         # Run the project's tests there
+  projectctl worktree list                      # Worktrees of all projects
+  projectctl worktree remove api@feature/login  # Delete it again")]
+    Worktree {
+        #[command(subcommand)]
+        action: WorktreeAction,
+    },
+
+    /// Interactive dashboard of all projects
+    #[command(long_about = "\
+Open a full-screen dashboard listing every project with its service
+status, git state and recent activity, refreshed every few seconds.
+
+Keys:
+  ↑/↓, j/k      Select a project
+  enter         Switch to it (leaves the dashboard)
+  s / x         Start / stop its services
+  l             Show the last lines of its service logs
+  r             Pick one of its commands and run it
+  R             Refresh now
+  q, esc        Quit
+
+Start, stop, logs and run show their output in the normal terminal and
+return to the dashboard when you press enter.
+
+Examples:
+  projectctl tui                         # Open the dashboard")]
+    Tui,
+
+    /// Manage the project status cache
+    #[command(long_about = "\
+Manage the status cache used by 'projectctl list'.
+
+The cache stores each project's last-known git status, service state and
+outdated-package count. 'warm' probes everything and is meant to run from
+cron or a shell prompt hook so 'list' always has fresh data.
+
+Examples:
+  projectctl cache warm                  # Refresh all projects
+  projectctl cache warm --no-deps        # Skip the slow outdated check
+  projectctl cache clear                 # Drop all cached status
+  */15 * * * * projectctl cache warm     # Crontab entry")]
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
     /// Generate shell completions
     #[command(long_about = "\
 Generate shell completion scripts for projectctl.
@@ -373,7 +730,7 @@ Examples:
 enum DepsAction {
     /// Update project dependencies
     Update {
-        /// Project name (omit for --all)
+        /// Project name (defaults to the current directory's project)
         name: Option<String>,
         /// Update all projects
         #[arg(short, long)]
@@ -381,7 +738,7 @@ enum DepsAction {
     },
     /// Check for outdated dependencies
     Check {
-        /// Project name (omit for --all)
+        /// Project name (defaults to the current directory's project)
         name: Option<String>,
         /// Check all projects
         #[arg(short, long)]
@@ -391,6 +748,144 @@ enum DepsAction {
     Summary,
 }
 
+#[derive(Subcommand)]
+enum ServicesAction {
+    /// Stop and remove containers and networks
+    Down {
+        /// Project name
+        name: String,
+        /// Also remove named volumes (deletes all data)
+        #[arg(long)]
+        volumes: bool,
+        /// Do not ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Recreate a service with its named volumes wiped
+    Reset {
+        /// Project name
+        name: String,
+        /// Service to reset
+        service: String,
+        /// Do not ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Snapshot a service's named volumes to tarballs
+    Backup {
+        /// Project name
+        name: String,
+        /// Service to back up
+        service: String,
+    },
+    /// Restore a service's named volumes from a snapshot
+    Restore {
+        /// Project name
+        name: String,
+        /// Service to restore
+        service: String,
+        /// Snapshot timestamp (defaults to the latest)
+        #[arg(long, value_name = "TIMESTAMP")]
+        from: Option<String>,
+        /// Do not ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum GitAction {
+    /// Fetch all remotes
+    Fetch {
+        /// Projects to fetch (default: current directory's project)
+        names: Vec<String>,
+        /// Fetch all registered projects
+        #[arg(short, long, conflicts_with = "names")]
+        all: bool,
+        /// Number of projects to fetch in parallel
+        #[arg(short, long, default_value_t = probe::MAX_WORKERS)]
+        jobs: usize,
+    },
+    /// Fast-forward the current branch to its upstream
+    Pull {
+        /// Projects to pull (default: current directory's project)
+        names: Vec<String>,
+        /// Pull all registered projects
+        #[arg(short, long, conflicts_with = "names")]
+        all: bool,
+        /// Only fast-forward (the only mode; accepted for familiarity)
+        #[arg(long)]
+        ff_only: bool,
+        /// Number of projects to pull in parallel
+        #[arg(short, long, default_value_t = probe::MAX_WORKERS)]
+        jobs: usize,
+    },
+    /// List local branches with age and merged status
+    Branches {
+        /// Projects to inspect (default: current directory's project)
+        names: Vec<String>,
+        /// Inspect all registered projects
+        #[arg(short, long, conflicts_with = "names")]
+        all: bool,
+    },
+    /// Delete merged or stale branches and prune missing worktrees
+    Prune {
+        /// Projects to clean up (default: current directory's project)
+        names: Vec<String>,
+        /// Clean up all registered projects
+        #[arg(short, long, conflicts_with = "names")]
+        all: bool,
+        /// Unmerged branches without commits for this many days are stale
+        #[arg(long, default_value_t = 90)]
+        days: i64,
+        /// Only show what would be removed
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+        /// Do not ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorktreeAction {
+    /// Check out a branch as a worktree and register it
+    Add {
+        /// Project name
+        project: String,
+        /// Branch to check out (created from the remote branch or HEAD if needed)
+        branch: String,
+        /// Directory of the worktree (default: next to the project)
+        #[arg(long)]
+        path: Option<String>,
+    },
+    /// List registered worktrees with their git status
+    List {
+        /// Only worktrees of this project
+        project: Option<String>,
+    },
+    /// Delete a worktree and unregister it
+    Remove {
+        /// Worktree as project@branch
+        name: String,
+        /// Delete even with uncommitted changes
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Probe all projects and store their status
+    Warm {
+        /// Skip checking for outdated dependencies
+        #[arg(long)]
+        no_deps: bool,
+    },
+    /// Remove all cached status
+    Clear,
+}
+
 #[derive(Subcommand)]
 enum TemplatesAction {
     /// Add a custom template
@@ -413,47 +908,107 @@ fn main() -> Result<()> {
     match cli.command {
         Commands::List {
             detailed,
+            git,
+            dirty,
+            unpushed,
             project_type,
             active,
-        } => cmd_list(&config, detailed, project_type, active)?,
+            refresh,
+        } => {
+            let filters = ListFilters {
+                project_type,
+                active,
+                dirty,
+                unpushed,
+            };
+            cmd_list(&config, detailed, git, &filters, refresh)?
+        }
 
         Commands::Switch {
             name,
             recent,
             code,
-        } => cmd_switch(&config, name, recent, code)?,
+            explain,
+        } => cmd_switch(&config, name, recent, code, explain)?,
 
         Commands::Info {
             name,
             git,
             deps,
             path_only,
-        } => cmd_info(&config, &name, git, deps, path_only)?,
+        } => cmd_info(&config, name.as_deref(), git, deps, path_only)?,
 
-        Commands::Start { name, service } => cmd_start(&config, &name, service.as_deref())?,
-        Commands::Stop { name, service } => cmd_stop(&config, &name, service.as_deref())?,
-        Commands::Restart { name, service } => cmd_restart(&config, &name, service.as_deref())?,
+        Commands::Start {
+            name,
+            service,
+            no_hooks,
+        } => cmd_start(&config, name.as_deref(), service.as_deref(), !no_hooks)?,
+        Commands::Stop {
+            name,
+            service,
+            no_hooks,
+        } => cmd_stop(&config, name.as_deref(), service.as_deref(), !no_hooks)?,
+        Commands::Restart { name, service } => {
+            cmd_restart(&config, name.as_deref(), service.as_deref())?
+        }
 
         Commands::Logs {
             name,
             service,
             follow,
             lines,
-        } => cmd_logs(&config, &name, service.as_deref(), follow, lines)?,
+        } => cmd_logs(&config, name.as_deref(), service.as_deref(), follow, lines)?,
+
+        Commands::Services { action } => cmd_services(&config, action)?,
+
+        Commands::Ps { stop_all_except } => cmd_ps(&config, stop_all_except.as_deref())?,
 
         Commands::Deps { action } => cmd_deps(&config, action)?,
 
         Commands::Run {
             name,
             command,
+            args,
             list,
-        } => cmd_run(&config, &name, command.as_deref(), list)?,
+            all,
+            jobs,
+            last,
+        } => {
+            if all {
+                // clap requires exactly one positional with --all
+                let command = name.context("--all needs a command")?;
+                cmd_run_all(&config, &command, &args, jobs)?
+            } else if last {
+                cmd_run_last(&config, name.as_deref())?
+            } else {
+                cmd_run(&config, name.as_deref(), command.as_deref(), &args, list)?
+            }
+        }
+
+        Commands::History { project, limit } => cmd_history(&config, project.as_deref(), limit)?,
+
+        Commands::Stats {
+            project,
+            failures,
+            limit,
+        } => cmd_stats(&config, project.as_deref(), failures, limit)?,
+
+        Commands::Exec {
+            names,
+            all,
+            project_type,
+            jobs,
+            command,
+        } => cmd_exec(&config, &names, all, project_type.as_deref(), jobs, &command)?,
 
         Commands::Add {
             name,
             path,
             project_type,
-        } => cmd_add(&config, name, path, project_type)?,
+            interactive,
+        } => cmd_add(&config, name, path, project_type, interactive)?,
+
+        Commands::Detect { path, explain } => cmd_detect(path.as_deref(), explain)?,
 
         Commands::Remove { name } => cmd_remove(&config, &name)?,
 
@@ -467,6 +1022,20 @@ fn main() -> Result<()> {
 
         Commands::Templates { action } => cmd_templates(&config, action)?,
 
+        Commands::Report {
+            since,
+            format,
+            author,
+        } => cmd_report(&config, &since, &format, author.as_deref())?,
+
+        Commands::Git { action } => cmd_git(&config, action)?,
+
+        Commands::Worktree { action } => cmd_worktree(&config, action)?,
+
+        Commands::Tui => cmd_tui(&config)?,
+
+        Commands::Cache { action } => cmd_cache(&config, action)?,
+
         Commands::Completions { shell } => cmd_completions(&shell)?,
     }
 
@@ -477,30 +1046,84 @@ fn main() -> Result<()> {
 // Command implementations
 // =========================================================================
 
+/// Which projects `list` shows
+struct ListFilters {
+    project_type: Option<String>,
+    active: bool,
+    dirty: bool,
+    unpushed: bool,
+}
+
 fn cmd_list(
     config: &ConfigManager,
     detailed: bool,
-    project_type: Option<String>,
-    active: bool,
+    git: bool,
+    filters: &ListFilters,
+    refresh: bool,
 ) -> Result<()> {
     let projects = config.load_projects()?;
 
     let filtered: Vec<Project> = projects
         .into_iter()
         .filter(|p| {
-            if let Some(ref pt) = project_type {
-                if !p.project_type.to_lowercase().contains(&pt.to_lowercase()) {
+            if let Some(ref pt) = filters.project_type {
+                if !p.has_type(pt) {
                     return false;
                 }
             }
-            if active && !p.has_docker_compose() {
+            if filters.active && !p.has_docker_compose() {
                 return false;
             }
             true
         })
         .collect();
 
-    display::display_project_list(&filtered, detailed);
+    // Git filters decide what is safe to leave, so they never trust cached
+    // git state; services may still come from the cache
+    let git_filter = filters.dirty || filters.unpushed;
+    let with_git = detailed || git || git_filter;
+    let cache = ProbeCache::new();
+    let mut status_cache = StatusCache::load(config)?;
+    let to_probe = if refresh {
+        filtered.clone()
+    } else {
+        status_cache.seed(&cache, &filtered, with_git && !git_filter)
+    };
+    if !to_probe.is_empty() {
+        status_cache.refresh(&cache, &to_probe, with_git);
+    }
+    if git_filter && !refresh {
+        let git_only: Vec<Project> = filtered
+            .iter()
+            .filter(|p| !to_probe.iter().any(|t| t.name == p.name))
+            .cloned()
+            .collect();
+        status_cache.refresh_git(&cache, &git_only);
+    }
+    if !to_probe.is_empty() || git_filter {
+        status_cache.save()?;
+    }
+
+    let filtered: Vec<Project> = filtered
+        .into_iter()
+        .filter(|p| !filters.active || cache.is_running(p))
+        .filter(|p| {
+            if !git_filter {
+                return true;
+            }
+            let Some(info) = cache.git_info(p) else {
+                return false;
+            };
+            (filters.dirty && !info.is_clean) || (filters.unpushed && info.has_unpushed())
+        })
+        .collect();
+
+    if git_filter && filtered.is_empty() {
+        println!("{} Nothing uncommitted or unpushed.", "✓".green());
+        return Ok(());
+    }
+
+    display::display_project_list(&filtered, detailed, git || git_filter, &cache, &status_cache);
     Ok(())
 }
 
@@ -509,8 +1132,20 @@ fn cmd_switch(
     name: Option<String>,
     recent: bool,
     code: bool,
+    explain: bool,
 ) -> Result<()> {
     let mut projects = config.load_projects()?;
+    let mut target = None;
+
+    let name = match name.as_deref() {
+        Some("-") => {
+            let events = activity::load(config)?;
+            let previous = activity::previous_switch(&events)
+                .context("No previous project to switch back to")?;
+            Some(previous.to_string())
+        }
+        _ => name,
+    };
 
     let project = if recent {
         // Find most recently used
@@ -532,18 +1167,34 @@ fn cmd_switch(
             eprintln!("{}", "Error: provide a project name or use --recent".red());
             std::process::exit(1);
         });
-        match projects.iter().position(|p| {
-            let q = query.to_lowercase();
-            p.name.to_lowercase() == q
-                || p.name.to_lowercase().starts_with(&q)
-                || p.name.to_lowercase().contains(&q)
-        }) {
-            Some(idx) => idx,
-            None => bail!("Project '{}' not found. Use 'projectctl list' to see registered projects.", query),
+        let ranking = activity::frecency(&projects, &activity::load(config)?);
+        if explain {
+            let root_query = query.split([':', '@']).next().unwrap_or(query);
+            display::display_switch_matches(root_query, &activity::rank_matches(&ranking, root_query));
+            return Ok(());
+        }
+
+        if query.contains(':') || query.contains('@') {
+            target = Some(config.resolve_project(&projects, Some(query))?);
+            let root = target.as_ref().unwrap().root_name();
+            match projects.iter().position(|p| p.name == root) {
+                Some(idx) => idx,
+                None => bail!("Project '{}' not found", root),
+            }
+        } else {
+            // Several fuzzy matches: the most frecent one wins
+            let best = activity::rank_matches(&ranking, query)
+                .first()
+                .and_then(|(_, best)| projects.iter().position(|p| p.name == best.project));
+            match best {
+                Some(idx) => idx,
+                None => bail!("Project '{}' not found. Use 'projectctl list' to see registered projects.", query),
+            }
         }
     };
 
-    let proj = &projects[project];
+    // A `project:package` target switches into the package directory
+    let proj = target.as_ref().unwrap_or(&projects[project]);
     let project_path = proj.expanded_path();
 
     if !project_path.exists() {
@@ -557,8 +1208,7 @@ fn cmd_switch(
 
     // Show directory
     println!(
-        "{} Changed directory\n   {}\n",
-        "📂".to_string(),
+        "📂 Changed directory\n   {}\n",
         project_path.display().to_string().dimmed()
     );
 
@@ -566,8 +1216,7 @@ fn cmd_switch(
     if let Some(venv) = proj.venv_path() {
         let python_version = get_python_version(&venv);
         println!(
-            "{} Activated Python venv\n   {} ({})\n",
-            "🐍".to_string(),
+            "🐍 Activated Python venv\n   {} ({})\n",
             venv.file_name().unwrap_or_default().to_string_lossy(),
             python_version
         );
@@ -575,33 +1224,45 @@ fn cmd_switch(
 
     // Check node version
     if proj.has_node_version() {
-        println!("{} Node.js version detected\n", "📦".to_string());
+        println!("📦 Node.js version detected\n");
     }
 
     // Git status
     if project_path.join(".git").exists() {
         if let Ok(git_info) = git::GitInfo::from_path(&project_path) {
-            println!("{} Git status", "🌿".to_string());
+            println!("🌿 Git status");
             println!("   Branch: {}", git_info.branch.cyan());
-            println!("   Status: {}\n", git_info.status_string());
+            println!("   Status: {}", git_info.status_string());
+            println!("   Upstream: {}", git_info.sync_string());
+            if let Some(ref state) = git_info.state {
+                println!("   {} {}", "In progress:".red().bold(), state);
+            }
+            if !git_info.conflicted.is_empty() {
+                println!("   Conflicts: {}", git_info.conflicted.join(", ").red());
+            }
+            if git_info.stash_count > 0 {
+                println!("   Stashes: {}", git_info.stash_count);
+            }
+            println!();
         }
     }
 
     // Open in VSCode if requested
     if code {
-        println!("{} Opening VSCode...\n", "💻".to_string());
+        println!("💻 Opening VSCode...\n");
         Command::new("code")
             .arg(&project_path)
             .spawn()
             .ok();
     }
 
-    // Update last_used
+    // Update last_used and log the switch
+    activity::record(config, &proj.name, activity::ActivityKind::Switch)?;
     projects[project].touch();
     config.save_projects(&projects)?;
 
     // Print the cd command for shell integration
-    println!("{} Ready to develop!", "✨".to_string());
+    println!("✨ Ready to develop!");
     println!(
         "\n{}",
         format!("# Run this or use the shell function:\ncd {}", project_path.display()).dimmed()
@@ -612,15 +1273,13 @@ fn cmd_switch(
 
 fn cmd_info(
     config: &ConfigManager,
-    name: &str,
+    name: Option<&str>,
     show_git: bool,
     show_deps: bool,
     path_only: bool,
 ) -> Result<()> {
     let projects = config.load_projects()?;
-    let project = config
-        .find_project(&projects, name)
-        .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))?;
+    let project = &config.resolve_project(&projects, name)?;
 
     if path_only {
         println!("{}", project.expanded_path().display());
@@ -631,11 +1290,11 @@ fn cmd_info(
 
     println!("{} {}", "Project:".bold(), project.name.cyan().bold());
     println!("{} {}", "Path:".bold(), project.path);
-    println!("{} {}", "Type:".bold(), project.project_type);
+    println!("{} {}", "Type:".bold(), project.type_label());
     println!();
 
     // Git info
-    if (show_git || (!show_git && !show_deps)) && project_path.join(".git").exists() {
+    if (show_git || !show_deps) && project_path.join(".git").exists() {
         println!("{}:", "Git".bold());
         match git::GitInfo::from_path(&project_path) {
             Ok(info) => info.display(),
@@ -649,21 +1308,7 @@ fn cmd_info(
         if project.has_docker_compose() {
             println!("{}:", "Services".bold());
             match services::get_compose_status(&project_path) {
-                Ok(svcs) if !svcs.is_empty() => {
-                    for (svc_name, state, ports) in &svcs {
-                        let icon = if state == "running" {
-                            "✓".green().to_string()
-                        } else {
-                            "✗".red().to_string()
-                        };
-                        let port_info = if ports.is_empty() {
-                            String::new()
-                        } else {
-                            format!("  ({})", ports)
-                        };
-                        println!("  {} {} {}{}", icon, svc_name, state, port_info);
-                    }
-                }
+                Ok(svcs) if !svcs.is_empty() => display::display_services(&svcs),
                 Ok(_) => println!("  No running services"),
                 Err(_) => println!("  Could not query docker compose"),
             }
@@ -689,14 +1334,44 @@ fn cmd_info(
     }
 
     // Deps info
-    if show_deps || (!show_git && !show_deps) {
-        if project_path.exists() {
-            let managers = deps::detect_managers(&project_path);
-            if !managers.is_empty() {
-                println!("{}:", "Dependencies".bold());
-                println!("  Managers: {}", managers.join(", "));
-                println!();
+    if (show_deps || !show_git) && project_path.exists() {
+        let managers = deps::detect_managers(&project_path);
+        if !managers.is_empty() {
+            println!("{}:", "Dependencies".bold());
+            println!("  Managers: {}", managers.join(", "));
+            println!();
+        }
+    }
+
+    // Monorepo packages
+    if !show_git && !show_deps {
+        let packages = project.sub_projects();
+        if !packages.is_empty() {
+            println!("{}:", "Packages".bold());
+            for package in &packages {
+                let path = Path::new(&package.path);
+                let rel = path.strip_prefix(&project_path).unwrap_or(path);
+                println!(
+                    "  {:<24} {:<12} {}",
+                    package.name.cyan(),
+                    package.project_type,
+                    rel.display().to_string().dimmed()
+                );
             }
+            println!();
+        }
+
+        let worktrees = project.worktree_projects();
+        if !worktrees.is_empty() {
+            println!("{}:", "Worktrees".bold());
+            for worktree in &worktrees {
+                println!(
+                    "  {:<24} {}",
+                    worktree.name.cyan(),
+                    worktree.path.dimmed()
+                );
+            }
+            println!();
         }
     }
 
@@ -704,7 +1379,7 @@ fn cmd_info(
     if !project.commands.is_empty() && !show_git && !show_deps {
         println!("{}:", "Commands".bold());
         for (cmd_name, cmd_val) in &project.commands {
-            println!("  {} = {}", cmd_name.cyan(), cmd_val.dimmed());
+            println!("  {} = {}", cmd_name.cyan(), cmd_val.to_string().dimmed());
         }
         println!();
     }
@@ -712,44 +1387,162 @@ fn cmd_info(
     Ok(())
 }
 
-fn cmd_start(config: &ConfigManager, name: &str, service: Option<&str>) -> Result<()> {
+fn cmd_start(
+    config: &ConfigManager,
+    name: Option<&str>,
+    service: Option<&str>,
+    hooks: bool,
+) -> Result<()> {
     let projects = config.load_projects()?;
-    let project = config
-        .find_project(&projects, name)
-        .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))?;
-    services::start_services(project, service)
+    let project = &config.resolve_project(&projects, name)?;
+    services::start_services(project, service, hooks)?;
+    activity::record(config, &project.name, activity::ActivityKind::Start)
 }
 
-fn cmd_stop(config: &ConfigManager, name: &str, service: Option<&str>) -> Result<()> {
+fn cmd_stop(
+    config: &ConfigManager,
+    name: Option<&str>,
+    service: Option<&str>,
+    hooks: bool,
+) -> Result<()> {
     let projects = config.load_projects()?;
-    let project = config
-        .find_project(&projects, name)
-        .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))?;
-    services::stop_services(project, service)
+    let project = &config.resolve_project(&projects, name)?;
+    services::stop_services(project, service, hooks)
 }
 
-fn cmd_restart(config: &ConfigManager, name: &str, service: Option<&str>) -> Result<()> {
+fn cmd_restart(config: &ConfigManager, name: Option<&str>, service: Option<&str>) -> Result<()> {
     let projects = config.load_projects()?;
-    let project = config
-        .find_project(&projects, name)
-        .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))?;
+    let project = &config.resolve_project(&projects, name)?;
     services::restart_services(project, service)
 }
 
 fn cmd_logs(
     config: &ConfigManager,
-    name: &str,
+    name: Option<&str>,
     service: Option<&str>,
     follow: bool,
     lines: usize,
 ) -> Result<()> {
     let projects = config.load_projects()?;
-    let project = config
-        .find_project(&projects, name)
-        .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))?;
+    let project = &config.resolve_project(&projects, name)?;
     services::show_logs(project, service, follow, lines)
 }
 
+fn cmd_services(config: &ConfigManager, action: ServicesAction) -> Result<()> {
+    let projects = config.load_projects()?;
+    let find = |name: &str| {
+        config
+            .find_project(&projects, name)
+            .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))
+    };
+
+    match action {
+        ServicesAction::Down { name, volumes, yes } => {
+            let project = find(&name)?;
+            if volumes
+                && !yes
+                && !display::confirm(&format!(
+                    "Delete all volumes (and their data) of {}?",
+                    project.name
+                ))
+            {
+                println!("Aborted.");
+                return Ok(());
+            }
+            services::down_services(project, volumes)?;
+        }
+        ServicesAction::Reset { name, service, yes } => {
+            let project = find(&name)?;
+            let volumes = services::service_volumes(project, &service)?;
+            if !volumes.is_empty() {
+                println!("Volumes that will be wiped:");
+                for volume in &volumes {
+                    println!("  {} ({})", volume.name.yellow(), volume.target.dimmed());
+                }
+                println!();
+            }
+            if !yes && !display::confirm(&format!("Reset {} of {}?", service, project.name)) {
+                println!("Aborted.");
+                return Ok(());
+            }
+            services::reset_service(project, &service)?;
+        }
+        ServicesAction::Backup { name, service } => {
+            let project = find(&name)?;
+            let backups_dir = config.backups_dir().join(&project.name);
+            services::backup_service(project, &service, &backups_dir)?;
+        }
+        ServicesAction::Restore {
+            name,
+            service,
+            from,
+            yes,
+        } => {
+            let project = find(&name)?;
+            let backups_dir = config.backups_dir().join(&project.name);
+            let snapshots = services::list_backups(&service, &backups_dir);
+            let snapshot = match from {
+                Some(ts) => snapshots
+                    .into_iter()
+                    .find(|p| p.file_name().is_some_and(|n| n.to_string_lossy() == ts))
+                    .ok_or_else(|| anyhow::anyhow!("No backup '{}' for {}", ts, service))?,
+                None => snapshots.into_iter().last().ok_or_else(|| {
+                    anyhow::anyhow!("No backups found for {} in {}", service, backups_dir.display())
+                })?,
+            };
+            if !yes
+                && !display::confirm(&format!(
+                    "Replace the current data of {} with {}?",
+                    service,
+                    snapshot.display()
+                ))
+            {
+                println!("Aborted.");
+                return Ok(());
+            }
+            services::restore_service(project, &service, &snapshot)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_ps(config: &ConfigManager, stop_all_except: Option<&str>) -> Result<()> {
+    let projects = config.load_projects()?;
+    let cache = ProbeCache::new();
+    let statuses = cache.all_compose_status(&projects);
+
+    if let Some(keep) = stop_all_except {
+        let keep = config
+            .find_project(&projects, keep)
+            .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", keep))?;
+        let mut stopped = 0;
+        for (project, svcs) in &statuses {
+            if project.name == keep.name || !svcs.iter().any(|s| s.state.is_running()) {
+                continue;
+            }
+            services::stop_services(project, None, true)?;
+            println!();
+            stopped += 1;
+        }
+        if stopped == 0 {
+            println!("{}", "Nothing else is running.".dimmed());
+        } else {
+            println!(
+                "{} Stopped {} project(s), kept {} running.",
+                "✓".green(),
+                stopped,
+                keep.name.cyan()
+            );
+        }
+        return Ok(());
+    }
+
+    let stats = services::get_container_stats().unwrap_or_default();
+    display::display_ps(&statuses, &stats);
+    Ok(())
+}
+
 fn cmd_deps(config: &ConfigManager, action: DepsAction) -> Result<()> {
     let projects = config.load_projects()?;
 
@@ -761,38 +1554,32 @@ fn cmd_deps(config: &ConfigManager, action: DepsAction) -> Result<()> {
                     println!();
                 }
             } else {
-                let query = name.as_deref().unwrap_or_else(|| {
-                    eprintln!(
-                        "{}",
-                        "Provide a project name or use --all".red()
-                    );
-                    std::process::exit(1);
-                });
-                let project = config
-                    .find_project(&projects, query)
-                    .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", query))?;
+                let project = &config.resolve_project(&projects, name.as_deref())?;
                 deps::update_deps(project)?;
             }
         }
         DepsAction::Check { name, all } => {
+            let mut status_cache = StatusCache::load(config)?;
             if all {
-                for project in &projects {
-                    deps::check_outdated(project)?;
+                let reports = probe::run_parallel(&projects, deps::collect_outdated);
+                for (project, report) in projects.iter().zip(reports) {
+                    match report {
+                        Ok(report) => {
+                            deps::print_outdated(project, &report);
+                            let count = deps::outdated_count(&report);
+                            status_cache.record_outdated(&project.name, count);
+                        }
+                        Err(e) => println!("{} {}: {}", "✗".red(), project.name, e),
+                    }
                     println!();
                 }
             } else {
-                let query = name.as_deref().unwrap_or_else(|| {
-                    eprintln!(
-                        "{}",
-                        "Provide a project name or use --all".red()
-                    );
-                    std::process::exit(1);
-                });
-                let project = config
-                    .find_project(&projects, query)
-                    .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", query))?;
-                deps::check_outdated(project)?;
+                let project = &config.resolve_project(&projects, name.as_deref())?;
+                let report = deps::collect_outdated(project)?;
+                deps::print_outdated(project, &report);
+                status_cache.record_outdated(&project.name, deps::outdated_count(&report));
             }
+            status_cache.save()?;
         }
         DepsAction::Summary => {
             deps::show_summary(&projects)?;
@@ -802,14 +1589,26 @@ fn cmd_deps(config: &ConfigManager, action: DepsAction) -> Result<()> {
     Ok(())
 }
 
-fn cmd_run(config: &ConfigManager, name: &str, command: Option<&str>, list: bool) -> Result<()> {
+fn cmd_run(
+    config: &ConfigManager,
+    name: Option<&str>,
+    command: Option<&str>,
+    args: &[String],
+    list: bool,
+) -> Result<()> {
     let projects = config.load_projects()?;
-    let project = config
-        .find_project(&projects, name)
-        .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))?;
+    let (project, command) = resolve_run_target(config, &projects, name, command)?;
+    let project = &project;
+
+    // Tasks from package.json, Makefile, etc. are runnable unless a
+    // configured command has the same name
+    let discovered: Vec<tasks::DetectedTask> = tasks::discover(&project.expanded_path())
+        .into_iter()
+        .filter(|t| !project.commands.contains_key(&t.name))
+        .collect();
 
     if list || command.is_none() {
-        if project.commands.is_empty() {
+        if project.commands.is_empty() && discovered.is_empty() {
             println!("{}", "No custom commands defined for this project.".yellow());
             println!("Add them in ~/.projectctl/projects.toml under [project.commands]");
             return Ok(());
@@ -818,44 +1617,565 @@ fn cmd_run(config: &ConfigManager, name: &str, command: Option<&str>, list: bool
             "Available commands for {}:\n",
             project.name.cyan().bold()
         );
-        for (cmd_name, cmd_val) in &project.commands {
-            println!("  {} → {}", cmd_name.bold(), cmd_val.dimmed());
+        let mut names: Vec<&String> = project.commands.keys().collect();
+        names.sort();
+        for cmd_name in names {
+            println!(
+                "  {} → {}",
+                cmd_name.bold(),
+                project.commands[cmd_name].to_string().dimmed()
+            );
+        }
+        if !discovered.is_empty() {
+            if !project.commands.is_empty() {
+                println!();
+            }
+            for task in &discovered {
+                println!(
+                    "  {} → {}  {}",
+                    task.name.bold(),
+                    task.command.dimmed(),
+                    format!("({})", task.source).cyan()
+                );
+            }
         }
         return Ok(());
     }
 
-    let cmd_name = command.unwrap();
-    let cmd_value = project.commands.get(cmd_name).ok_or_else(|| {
-        anyhow::anyhow!(
+    run_recorded(config, project, command.unwrap(), args)
+}
+
+/// Work out the project and command of `run [name] [command]`. A single
+/// argument that is not a project name but a command of the current
+/// directory's project is taken as the command.
+fn resolve_run_target<'a>(
+    config: &ConfigManager,
+    projects: &[Project],
+    name: Option<&'a str>,
+    command: Option<&'a str>,
+) -> Result<(Project, Option<&'a str>)> {
+    if let (Some(name), None) = (name, command) {
+        let is_project_name = projects.iter().any(|p| p.name.eq_ignore_ascii_case(name));
+        if !is_project_name && !name.contains([':', '@']) {
+            if let Ok(current) = config.resolve_project(projects, None) {
+                if with_discovered_tasks(&current).commands.contains_key(name) {
+                    return Ok((current, Some(name)));
+                }
+            }
+        }
+    }
+    Ok((config.resolve_project(projects, name)?, command))
+}
+
+/// Run a project command in the foreground and record it in the history
+fn run_recorded(
+    config: &ConfigManager,
+    project: &Project,
+    cmd_name: &str,
+    args: &[String],
+) -> Result<()> {
+    let project = with_discovered_tasks(project);
+    if !project.commands.contains_key(cmd_name) {
+        bail!(
             "Command '{}' not found for project '{}'. Use --list to see available commands.",
             cmd_name,
             project.name
-        )
-    })?;
+        );
+    }
 
-    let project_path = project.expanded_path();
-    println!(
-        "Running: {} {}\n",
-        project.name.cyan().bold(),
-        cmd_name.bold()
-    );
-    println!(
-        "Executing: {}\n",
-        cmd_value.dimmed()
-    );
+    let record = history::RunRecord::new(&project.name, cmd_name, args, chrono::Utc::now());
+    let started = std::time::Instant::now();
+    let outcome = runner::run_command(&project, cmd_name, args);
+    let exit_code = outcome.as_ref().ok().copied();
+    history::append(config, &record.finish(started.elapsed(), exit_code))?;
+    activity::record(config, &project.name, activity::ActivityKind::Run)?;
+
+    match outcome? {
+        0 => Ok(()),
+        code => bail!("Command '{}' exited with code {}", cmd_name, code),
+    }
+}
+
+fn cmd_run_last(config: &ConfigManager, name: Option<&str>) -> Result<()> {
+    let projects = config.load_projects()?;
+    let records = history::load(config)?;
+
+    // Outside a project directory, fall back to the latest run of any project
+    let scope = match name {
+        Some(_) => Some(config.resolve_project(&projects, name)?),
+        None => config.resolve_project(&projects, None).ok(),
+    };
+    let last = match scope {
+        Some(project) => records
+            .iter()
+            .rev()
+            .find(|r| r.project == project.name)
+            .ok_or_else(|| anyhow::anyhow!("No recorded runs for '{}'", project.name))?,
+        None => records
+            .last()
+            .ok_or_else(|| anyhow::anyhow!("No recorded runs yet."))?,
+    };
+
+    let project = config
+        .resolve_project(&projects, Some(&last.project))
+        .with_context(|| format!("Project '{}' is no longer registered", last.project))?;
+    run_recorded(config, &project, &last.command, &last.args)
+}
+
+/// Recorded runs of `project`, or of all projects when none is given
+fn project_history(config: &ConfigManager, project: Option<&str>) -> Result<Vec<history::RunRecord>> {
+    let projects = config.load_projects()?;
+    let project_name = match project {
+        Some(name) => Some(
+            config
+                .find_project(&projects, name)
+                .map(|p| p.name.clone())
+                .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))?,
+        ),
+        None => None,
+    };
+
+    Ok(history::load(config)?
+        .into_iter()
+        .filter(|r| project_name.as_ref().is_none_or(|n| &r.project == n))
+        .collect())
+}
 
-    let status = Command::new("sh")
-        .args(["-c", cmd_value])
-        .current_dir(&project_path)
-        .stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit())
-        .stdin(std::process::Stdio::inherit())
-        .status()?;
+fn cmd_history(config: &ConfigManager, project: Option<&str>, limit: usize) -> Result<()> {
+    let records = project_history(config, project)?;
+    let start = records.len().saturating_sub(limit);
+    display::display_history(&records[start..]);
+    Ok(())
+}
+
+fn cmd_stats(
+    config: &ConfigManager,
+    project: Option<&str>,
+    failures: bool,
+    limit: usize,
+) -> Result<()> {
+    let records = project_history(config, project)?;
+    let mut stats = history::stats(&records);
+    if failures {
+        stats.sort_by(|a, b| b.failures.cmp(&a.failures).then(b.runs.cmp(&a.runs)));
+    } else {
+        stats.sort_by_key(|s| std::cmp::Reverse(s.avg_ms));
+    }
+    stats.truncate(limit);
+    display::display_stats(&stats);
+    Ok(())
+}
+
+/// A copy of the project whose commands include its discovered tasks
+/// (configured commands win on name clashes)
+fn with_discovered_tasks(project: &Project) -> Project {
+    let mut project = project.clone();
+    for task in tasks::discover(&project.expanded_path()) {
+        project
+            .commands
+            .entry(task.name)
+            .or_insert_with(|| task.command.into());
+    }
+    project
+}
+
+fn cmd_run_all(config: &ConfigManager, command: &str, args: &[String], jobs: usize) -> Result<()> {
+    let projects: Vec<Project> = config
+        .load_projects()?
+        .iter()
+        .filter(|p| p.exists())
+        .map(with_discovered_tasks)
+        .filter(|p| p.commands.contains_key(command))
+        .collect();
+
+    if projects.is_empty() {
+        bail!("No project defines a '{}' command.", command);
+    }
+
+    let job = runner::BatchJob::Command {
+        name: command,
+        args,
+    };
+    run_batch_and_report(config, &projects, &job, command, jobs)
+}
 
-    if !status.success() {
-        bail!("Command exited with status: {}", status);
+fn cmd_exec(
+    config: &ConfigManager,
+    names: &[String],
+    all: bool,
+    project_type: Option<&str>,
+    jobs: usize,
+    command: &[String],
+) -> Result<()> {
+    let projects = config.load_projects()?;
+    let selected = if all || project_type.is_some() || !names.is_empty() {
+        select_projects(config, &projects, names, all, project_type)?
+    } else {
+        bail!("Select projects by name, with --type, or with --all");
+    };
+    if selected.is_empty() {
+        bail!("No projects to run in.");
     }
 
+    // A single argument is a complete shell string; several are quoted as words
+    let script = match command {
+        [single] => single.clone(),
+        words => words
+            .iter()
+            .map(|w| runner::shell_quote(w))
+            .collect::<Vec<_>>()
+            .join(" "),
+    };
+    run_batch_and_report(config, &selected, &runner::BatchJob::Shell(&script), &script, jobs)
+}
+
+/// Projects chosen by `--all`, `--type` or by name (the current directory's
+/// project when none is given). Missing directories are skipped with a warning.
+fn select_projects(
+    config: &ConfigManager,
+    projects: &[Project],
+    names: &[String],
+    all: bool,
+    project_type: Option<&str>,
+) -> Result<Vec<Project>> {
+    let selected: Vec<Project> = if all {
+        projects.to_vec()
+    } else if let Some(pt) = project_type {
+        projects.iter().filter(|p| p.has_type(pt)).cloned().collect()
+    } else if !names.is_empty() {
+        // A project named twice runs once
+        let mut selected: Vec<Project> = Vec::new();
+        for name in names {
+            let project = config.resolve_project(projects, Some(name))?;
+            if !selected.iter().any(|p| p.name == project.name) {
+                selected.push(project);
+            }
+        }
+        selected
+    } else {
+        vec![config.resolve_project(projects, None)?]
+    };
+
+    let (selected, missing): (Vec<Project>, Vec<Project>) =
+        selected.into_iter().partition(|p| p.exists());
+    for p in &missing {
+        println!("{} Skipping {}: directory not found", "⚠".yellow(), p.name);
+    }
+    Ok(selected)
+}
+
+fn cmd_report(
+    config: &ConfigManager,
+    since: &str,
+    format: &str,
+    author: Option<&str>,
+) -> Result<()> {
+    let since = report::parse_since(since)?;
+    let projects = config.load_projects()?;
+    let records = history::load(config)?;
+    let events = activity::load(config)?;
+    let reports = report::build(&projects, &records, &events, since, author);
+    display::display_report(&reports, since, format);
+    Ok(())
+}
+
+fn cmd_tui(config: &ConfigManager) -> Result<()> {
+    let mut selected: Option<String> = None;
+    loop {
+        let projects: Vec<Project> = config
+            .load_projects()?
+            .iter()
+            .map(with_discovered_tasks)
+            .collect();
+        let action = tui::run(config, projects, selected.as_deref())?;
+        let result = match action {
+            tui::Action::Quit => return Ok(()),
+            tui::Action::Switch(name) => return cmd_switch(config, Some(name), false, false, false),
+            tui::Action::Start(ref name) => cmd_start(config, Some(name), None, true),
+            tui::Action::Stop(ref name) => cmd_stop(config, Some(name), None, true),
+            tui::Action::Logs(ref name) => cmd_logs(config, Some(name), None, false, 100),
+            tui::Action::Run(ref name, ref command) => {
+                let projects = config.load_projects()?;
+                let project = config.resolve_project(&projects, Some(name))?;
+                run_recorded(config, &project, command, &[])
+            }
+        };
+        if let Err(e) = result {
+            println!("{} {:#}", "✗".red(), e);
+        }
+
+        print!("\n{}", "Press enter to return to the dashboard...".dimmed());
+        std::io::Write::flush(&mut std::io::stdout()).ok();
+        std::io::stdin().read_line(&mut String::new()).ok();
+
+        selected = match action {
+            tui::Action::Start(name)
+            | tui::Action::Stop(name)
+            | tui::Action::Logs(name)
+            | tui::Action::Run(name, _) => Some(name),
+            _ => None,
+        };
+    }
+}
+
+fn cmd_git(config: &ConfigManager, action: GitAction) -> Result<()> {
+    let projects = config.load_projects()?;
+
+    match action {
+        GitAction::Fetch { names, all, jobs } => {
+            let selected = git_repos(select_projects(config, &projects, &names, all, None)?, all);
+            println!("Fetching {} repositories...\n", selected.len());
+            let results = probe::run_parallel_limit(&selected, jobs, |p| {
+                git::fetch_all(&p.expanded_path()).map(|n| match n {
+                    0 => "skipped: no remotes".to_string(),
+                    1 => "fetched 1 remote".to_string(),
+                    n => format!("fetched {} remotes", n),
+                })
+            });
+            report_git_results(&selected, results)
+        }
+        GitAction::Pull {
+            names, all, jobs, ..
+        } => {
+            let selected = git_repos(select_projects(config, &projects, &names, all, None)?, all);
+            println!("Pulling {} repositories (fast-forward only)...\n", selected.len());
+            let results = probe::run_parallel_limit(&selected, jobs, |p| {
+                git::pull_ff_only(&p.expanded_path()).map(|outcome| outcome.to_string())
+            });
+            report_git_results(&selected, results)
+        }
+        GitAction::Branches { names, all } => {
+            let selected = git_repos(select_projects(config, &projects, &names, all, None)?, all);
+            let branches: Vec<(String, Vec<git::BranchInfo>)> = selected
+                .iter()
+                .filter_map(|p| match git::list_branches(&p.expanded_path()) {
+                    Ok(list) => Some((p.name.clone(), list)),
+                    Err(e) => {
+                        println!("{} {}: {}", "✗".red(), p.name, e);
+                        None
+                    }
+                })
+                .collect();
+            display::display_branches(&branches);
+            Ok(())
+        }
+        GitAction::Prune {
+            names,
+            all,
+            days,
+            dry_run,
+            yes,
+        } => {
+            let selected = git_repos(select_projects(config, &projects, &names, all, None)?, all);
+            let mut pruned: Vec<Project> = Vec::new();
+            let mut candidates: Vec<(String, Vec<git::PruneCandidate>)> = Vec::new();
+            for project in selected {
+                match git::prune_candidates(&project.expanded_path(), days) {
+                    Ok(list) if !list.is_empty() => {
+                        candidates.push((project.name.clone(), list));
+                        pruned.push(project);
+                    }
+                    Ok(_) => {}
+                    Err(e) => println!("{} {}: {}", "✗".red(), project.name, e),
+                }
+            }
+
+            let total: usize = candidates.iter().map(|(_, list)| list.len()).sum();
+            if total == 0 {
+                println!("{} Nothing to prune.", "✓".green());
+                return Ok(());
+            }
+            display::display_prune_candidates(&candidates);
+            if dry_run {
+                println!("\n{}", "Dry run: nothing was deleted.".dimmed());
+                return Ok(());
+            }
+            let stale = candidates
+                .iter()
+                .flat_map(|(_, list)| list)
+                .filter(|c| matches!(c, git::PruneCandidate::StaleBranch(_)))
+                .count();
+            if stale > 0 {
+                println!(
+                    "\n{} {} stale branch(es) are unmerged; their unmerged commits will be lost.",
+                    "⚠".yellow(),
+                    stale
+                );
+            }
+            if !yes && !display::confirm(&format!("\nRemove {} item(s)?", total)) {
+                println!("Aborted.");
+                return Ok(());
+            }
+
+            let results: Vec<Result<String>> = pruned
+                .iter()
+                .zip(&candidates)
+                .map(|(project, (_, list))| {
+                    git::prune(&project.expanded_path(), list).map(|removed| match removed {
+                        (b, 0) => format!("deleted {} branch(es)", b),
+                        (0, w) => format!("pruned {} worktree(s)", w),
+                        (b, w) => format!("deleted {} branch(es), pruned {} worktree(s)", b, w),
+                    })
+                })
+                .collect();
+            println!();
+            report_git_results(&pruned, results)
+        }
+    }
+}
+
+/// Drop projects that are not git repositories. With --all they are
+/// skipped silently; explicitly selected ones get a warning.
+fn git_repos(projects: Vec<Project>, all: bool) -> Vec<Project> {
+    projects
+        .into_iter()
+        .filter(|p| {
+            let is_repo = p.expanded_path().join(".git").exists();
+            if !is_repo && !all {
+                println!("{} Skipping {}: not a git repository", "⚠".yellow(), p.name);
+            }
+            is_repo
+        })
+        .collect()
+}
+
+fn cmd_worktree(config: &ConfigManager, action: WorktreeAction) -> Result<()> {
+    let mut projects = config.load_projects()?;
+
+    match action {
+        WorktreeAction::Add {
+            project,
+            branch,
+            path,
+        } => {
+            let root = config
+                .find_project(&projects, &project)
+                .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", project))?;
+            if root.worktrees.contains_key(&branch) {
+                bail!("{}@{} is already registered", root.name, branch);
+            }
+            let root_path = root.expanded_path();
+            if !root_path.join(".git").is_dir() {
+                bail!("{} is not the main checkout of a git repository", root_path.display());
+            }
+            let dest = match path {
+                Some(p) => ConfigManager::expand_path(&p),
+                None => {
+                    let dir = root_path.file_name().unwrap_or_default().to_string_lossy();
+                    root_path.with_file_name(format!("{}-{}", dir, branch.replace('/', "-")))
+                }
+            };
+
+            let origin = git::add_worktree(&root_path, &branch, &dest)?;
+            let name = root.name.clone();
+            let root = projects.iter_mut().find(|p| p.name == name).unwrap();
+            root.worktrees
+                .insert(branch.clone(), dest.to_string_lossy().to_string());
+            config.save_projects(&projects)?;
+
+            println!(
+                "{} Created worktree {} ({})",
+                "✓".green(),
+                format!("{}@{}", name, branch).cyan(),
+                origin
+            );
+            println!("  {}", dest.display().to_string().dimmed());
+        }
+        WorktreeAction::List { project } => {
+            let roots: Vec<&Project> = match project {
+                Some(name) => vec![config
+                    .find_project(&projects, &name)
+                    .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))?],
+                None => projects.iter().collect(),
+            };
+            let worktrees: Vec<Project> = roots.iter().flat_map(|p| p.worktree_projects()).collect();
+            let status = probe::run_parallel(&worktrees, |wt| {
+                let path = wt.expanded_path();
+                if !path.exists() {
+                    None
+                } else {
+                    git::GitInfo::from_path(&path).ok()
+                }
+            });
+            display::display_worktrees(&worktrees, &status);
+        }
+        WorktreeAction::Remove { name, force } => {
+            let worktree = config.resolve_project(&projects, Some(&name))?;
+            let Some((root_name, branch)) = worktree.name.split_once('@') else {
+                bail!("'{}' is not a worktree. Use project@branch.", worktree.name);
+            };
+            if branch.contains(':') {
+                bail!("'{}' is a package of a worktree. Use project@branch.", worktree.name);
+            }
+            let root = projects.iter_mut().find(|p| p.name == root_name).unwrap();
+            let path = worktree.expanded_path();
+            if path.exists() {
+                git::remove_worktree(&root.expanded_path(), &path, force)?;
+            } else {
+                // Directory already gone: clear git's record of it if it has one
+                git::remove_worktree(&root.expanded_path(), &path, true).ok();
+            }
+            root.worktrees.remove(branch);
+            config.save_projects(&projects)?;
+            println!(
+                "{} Removed worktree {} (branch {} is kept)",
+                "✓".green(),
+                worktree.name.cyan(),
+                branch
+            );
+        }
+    }
+    Ok(())
+}
+
+fn report_git_results(projects: &[Project], results: Vec<Result<String>>) -> Result<()> {
+    let failed = results.iter().filter(|r| r.is_err()).count();
+    let rows: Vec<(String, std::result::Result<String, String>)> = projects
+        .iter()
+        .zip(results)
+        .map(|(p, r)| (p.name.clone(), r.map_err(|e| format!("{:#}", e))))
+        .collect();
+    display::display_git_results(&rows);
+
+    if failed > 0 {
+        bail!("{} of {} projects failed", failed, projects.len());
+    }
+    Ok(())
+}
+
+/// Run a batch job, print the summary table, and fail if any project failed
+fn run_batch_and_report(
+    config: &ConfigManager,
+    projects: &[Project],
+    job: &runner::BatchJob,
+    label: &str,
+    jobs: usize,
+) -> Result<()> {
+    let log_dir = config
+        .logs_dir()
+        .join(chrono::Local::now().format("%Y%m%d-%H%M%S-%3f").to_string());
+
+    println!(
+        "Running {} in {} project(s)\n",
+        label.bold(),
+        projects.len().to_string().cyan()
+    );
+    let started = chrono::Utc::now();
+    let results = runner::run_batch(projects, job, jobs, &log_dir)?;
+    display::display_batch_summary(&results);
+
+    if let runner::BatchJob::Command { name, args } = job {
+        for result in &results {
+            let record = history::RunRecord::new(&result.project, name, args, started)
+                .finish(result.duration, result.exit_code);
+            history::append(config, &record)?;
+        }
+    }
+
+    let failed = results.iter().filter(|r| !r.success()).count();
+    if failed > 0 {
+        bail!("{} of {} projects failed", failed, results.len());
+    }
     Ok(())
 }
 
@@ -864,6 +2184,7 @@ fn cmd_add(
     name: Option<String>,
     path: Option<String>,
     project_type: Option<String>,
+    interactive: bool,
 ) -> Result<()> {
     let project_path = match path {
         Some(p) => ConfigManager::expand_path(&p),
@@ -882,14 +2203,21 @@ fn cmd_add(
             .to_string()
     });
 
-    let detected_type = project_type.unwrap_or_else(|| Project::detect_type(&project_path));
+    if project_name.contains([':', '@']) {
+        bail!("Project names cannot contain ':' or '@' (they separate project and package or branch)");
+    }
+
+    let stacks = Project::detect_stacks(&project_path, project_type.as_deref());
+    let detected_type = stacks
+        .first()
+        .map_or_else(|| "unknown".to_string(), |s| s.project_type.clone());
     let detected_services = Project::detect_services(&project_path);
-    let detected_commands = Project::detect_commands(&project_path, &detected_type);
+    let detected_commands = Project::detect_stack_commands(&project_path, &stacks);
 
     let mut projects = config.load_projects()?;
 
-    // Check if already registered
-    if projects.iter().any(|p| p.name == project_name) {
+    // Check if already registered (the wizard asks for another name)
+    if !interactive && projects.iter().any(|p| p.name == project_name) {
         bail!(
             "Project '{}' already registered. Remove it first to re-add.",
             project_name
@@ -901,13 +2229,23 @@ fn cmd_add(
         project_path.to_string_lossy().to_string(),
         detected_type.clone(),
     );
+    project.stacks = project::stack_types(&stacks);
     project.services = detected_services;
-    project.commands = detected_commands;
+    project.commands = detected_commands
+        .into_iter()
+        .map(|(name, cmd)| (name, cmd.into()))
+        .collect();
+    project.packages = Project::initial_packages(&project_path, &detected_type);
+    if interactive && !wizard::review(&mut project, &projects)? {
+        println!("Aborted.");
+        return Ok(());
+    }
+    let packages = project.sub_projects();
 
     println!("{} Project added!\n", "✓".green().bold());
     println!("  Name:     {}", project.name.cyan());
     println!("  Path:     {}", project.path);
-    println!("  Type:     {}", detected_type);
+    println!("  Type:     {}", project.type_label());
     if !project.services.is_empty() {
         println!("  Services: {}", project.services.join(", "));
     }
@@ -915,6 +2253,13 @@ fn cmd_add(
         let cmds: Vec<String> = project.commands.keys().cloned().collect();
         println!("  Commands: {}", cmds.join(", "));
     }
+    if !packages.is_empty() {
+        let names: Vec<String> = packages
+            .iter()
+            .map(|p| format!("{} ({})", p.name, p.type_label()))
+            .collect();
+        println!("  Packages: {}", names.join(", "));
+    }
 
     projects.push(project);
     config.save_projects(&projects)?;
@@ -922,6 +2267,34 @@ fn cmd_add(
     Ok(())
 }
 
+fn cmd_detect(path: Option<&str>, explain: bool) -> Result<()> {
+    let path = match path {
+        Some(p) => ConfigManager::expand_path(p),
+        None => std::env::current_dir()?,
+    };
+    if !path.is_dir() {
+        bail!("Path is not a directory: {}", path.display());
+    }
+
+    let matches = detect::explain(&path);
+    let stacks = detect::stacks_of(&matches);
+    let project_type = stacks.first().map_or("unknown", |s| s.project_type.as_str());
+    println!("{} {}", "Type:".bold(), project_type.cyan().bold());
+    if stacks.len() > 1 {
+        let names: Vec<String> = stacks
+            .iter()
+            .map(|s| format!("{} ({})", s.name, s.project_type))
+            .collect();
+        println!("{} {}", "Stacks:".bold(), names.join(", "));
+    }
+
+    if explain {
+        println!();
+        display::display_detection(&matches);
+    }
+    Ok(())
+}
+
 fn cmd_remove(config: &ConfigManager, name: &str) -> Result<()> {
     let mut projects = config.load_projects()?;
     let original_len = projects.len();
@@ -947,7 +2320,8 @@ fn cmd_remove(config: &ConfigManager, name: &str) -> Result<()> {
 
 fn cmd_recent(config: &ConfigManager, limit: usize) -> Result<()> {
     let projects = config.load_projects()?;
-    display::display_recent(&projects, limit);
+    let events = activity::load(config)?;
+    display::display_recent(&activity::frecency(&projects, &events), limit);
     Ok(())
 }
 
@@ -968,6 +2342,43 @@ fn cmd_templates(config: &ConfigManager, action: Option<TemplatesAction>) -> Res
     Ok(())
 }
 
+fn cmd_cache(config: &ConfigManager, action: CacheAction) -> Result<()> {
+    let projects = config.load_projects()?;
+    let mut status_cache = StatusCache::load(config)?;
+
+    match action {
+        CacheAction::Warm { no_deps } => {
+            let existing: Vec<Project> = projects.iter().filter(|p| p.exists()).cloned().collect();
+            let cache = ProbeCache::new();
+            status_cache.retain_projects(&projects);
+            status_cache.refresh(&cache, &existing, true);
+
+            if !no_deps {
+                let reports = probe::run_parallel(&existing, deps::collect_outdated);
+                for (project, report) in existing.iter().zip(reports) {
+                    if let Ok(report) = report {
+                        status_cache.record_outdated(&project.name, deps::outdated_count(&report));
+                    }
+                }
+            }
+
+            status_cache.save()?;
+            println!(
+                "{} Cached status for {} project(s).",
+                "✓".green(),
+                existing.len()
+            );
+        }
+        CacheAction::Clear => {
+            status_cache.clear();
+            status_cache.save()?;
+            println!("{} Status cache cleared.", "✓".green());
+        }
+    }
+
+    Ok(())
+}
+
 fn cmd_completions(shell: &str) -> Result<()> {
     use clap::CommandFactory;
     let mut cmd = Cli::command();
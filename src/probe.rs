@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::git::GitInfo;
+use crate::project::Project;
+use crate::services::{self, ServiceStatus};
+
+/// Upper bound on concurrent probes (each one usually spawns a subprocess)
+pub const MAX_WORKERS: usize = 8;
+
+/// Run `f` over `items` on a bounded pool of worker threads.
+/// Results are returned in the same order as `items`.
+pub fn run_parallel<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    run_parallel_limit(items, MAX_WORKERS, f)
+}
+
+/// Like `run_parallel`, with an explicit number of workers
+pub fn run_parallel_limit<T, R, F>(items: &[T], limit: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = limit.max(1).min(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..items.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap()[idx] = Some(result);
+            });
+        }
+    });
+
+    results.into_inner().unwrap().into_iter().flatten().collect()
+}
+
+/// Per-invocation cache of per-project probes, so a command never asks
+/// docker or libgit2 the same question twice
+#[derive(Default)]
+pub struct ProbeCache {
+    compose: Mutex<HashMap<String, Vec<ServiceStatus>>>,
+    git: Mutex<HashMap<String, Option<GitInfo>>>,
+}
+
+impl ProbeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Docker compose status of a project (empty if it has no compose file)
+    pub fn compose_status(&self, project: &Project) -> Vec<ServiceStatus> {
+        if let Some(svcs) = self.compose.lock().unwrap().get(&project.name) {
+            return svcs.clone();
+        }
+        let svcs = if project.exists() && project.has_docker_compose() {
+            services::get_compose_status(&project.expanded_path()).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        self.compose
+            .lock()
+            .unwrap()
+            .insert(project.name.clone(), svcs.clone());
+        svcs
+    }
+
+    /// Whether any compose service of the project is running
+    pub fn is_running(&self, project: &Project) -> bool {
+        self.compose_status(project)
+            .iter()
+            .any(|s| s.state.is_running())
+    }
+
+    /// Git information of a project, if it is a repository
+    pub fn git_info(&self, project: &Project) -> Option<GitInfo> {
+        if let Some(info) = self.git.lock().unwrap().get(&project.name) {
+            return info.clone();
+        }
+        let path = project.expanded_path();
+        let info = if path.join(".git").exists() {
+            GitInfo::from_path(&path).ok()
+        } else {
+            None
+        };
+        self.git
+            .lock()
+            .unwrap()
+            .insert(project.name.clone(), info.clone());
+        info
+    }
+
+    /// Record a compose status obtained elsewhere (e.g. the on-disk cache)
+    pub fn insert_compose(&self, project: &Project, svcs: Vec<ServiceStatus>) {
+        self.compose.lock().unwrap().insert(project.name.clone(), svcs);
+    }
+
+    /// Record git information obtained elsewhere (e.g. the on-disk cache)
+    pub fn insert_git(&self, project: &Project, info: Option<GitInfo>) {
+        self.git.lock().unwrap().insert(project.name.clone(), info);
+    }
+
+    /// Query compose status for all projects in parallel
+    pub fn prefetch_compose(&self, projects: &[Project]) {
+        run_parallel(projects, |p| {
+            self.compose_status(p);
+        });
+    }
+
+    /// Read git information for all projects in parallel
+    pub fn prefetch_git(&self, projects: &[Project]) {
+        run_parallel(projects, |p| {
+            self.git_info(p);
+        });
+    }
+
+    /// Read git information for all projects again, replacing what was
+    /// recorded for them
+    pub fn reprobe_git(&self, projects: &[Project]) {
+        {
+            let mut git = self.git.lock().unwrap();
+            for project in projects {
+                git.remove(&project.name);
+            }
+        }
+        self.prefetch_git(projects);
+    }
+
+    /// Projects that have at least one compose service, with their status
+    pub fn all_compose_status<'a>(
+        &self,
+        projects: &'a [Project],
+    ) -> Vec<(&'a Project, Vec<ServiceStatus>)> {
+        self.prefetch_compose(projects);
+        projects
+            .iter()
+            .map(|p| (p, self.compose_status(p)))
+            .filter(|(_, svcs)| !svcs.is_empty())
+            .collect()
+    }
+}
@@ -1,9 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use crate::config::ConfigManager;
+use crate::detect::{self, DetectedStack};
+use crate::display;
+use crate::tasks;
+use crate::workspace;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Project {
@@ -12,16 +16,151 @@ pub struct Project {
     #[serde(default = "default_project_type")]
     #[serde(rename = "type")]
     pub project_type: String,
+    /// All detected types of a polyglot project, primary first
+    /// (e.g. `["tauri", "react-vite"]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stacks: Vec<String>,
     #[serde(default)]
     pub services: Vec<String>,
     #[serde(default)]
     pub env: HashMap<String, String>,
     #[serde(default)]
-    pub commands: HashMap<String, String>,
+    pub commands: HashMap<String, ProjectCommand>,
+    #[serde(default, skip_serializing_if = "Hooks::is_empty")]
+    pub hooks: Hooks,
+    /// Sub-packages of a monorepo, in addition to discovered workspace members
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub packages: HashMap<String, Package>,
+    /// Git worktrees of the project by branch, addressed as `project@branch`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub worktrees: HashMap<String, String>,
     #[serde(default)]
     pub last_used: Option<String>,
 }
 
+/// A sub-package of a monorepo project, addressed as `project:name`
+///
+/// ```toml
+/// [project.packages.frontend]
+/// path = "frontend"
+/// [project.packages.backend]
+/// path = "backend"
+/// type = "fastapi"
+/// commands = { dev = "uvicorn app.main:app --reload --port 8001" }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Package {
+    /// Directory relative to the project root
+    pub path: String,
+    /// Detected from the package directory when not set
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub package_type: Option<String>,
+    /// Added to (and overriding) the commands detected for the package
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub commands: HashMap<String, ProjectCommand>,
+}
+
+/// A custom project command: either a plain shell string or a table
+///
+/// ```toml
+/// [project.commands]
+/// test = "pytest {args}"
+/// web = { cmd = "npm run dev", cwd = "frontend", env_file = ".env.dev" }
+/// build = { depends = ["lint"], steps = ["npm run build", "npm run size"] }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum ProjectCommand {
+    Shell(String),
+    Detailed(CommandDef),
+}
+
+/// Table form of a project command
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CommandDef {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub cmd: String,
+    /// Shell strings run in order before `cmd`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<String>,
+    /// Other commands of the project that must succeed first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends: Vec<String>,
+    /// Working directory relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    /// Extra dotenv file, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<String>,
+}
+
+impl ProjectCommand {
+    /// The command in table form
+    pub fn def(&self) -> CommandDef {
+        match self {
+            Self::Shell(cmd) => CommandDef {
+                cmd: cmd.clone(),
+                ..Default::default()
+            },
+            Self::Detailed(def) => def.clone(),
+        }
+    }
+}
+
+impl CommandDef {
+    /// Shell strings to execute, in order: `steps`, then `cmd`
+    pub fn scripts(&self) -> Vec<String> {
+        let mut scripts = self.steps.clone();
+        if !self.cmd.is_empty() {
+            scripts.push(self.cmd.clone());
+        }
+        scripts
+    }
+}
+
+impl From<String> for ProjectCommand {
+    fn from(cmd: String) -> Self {
+        Self::Shell(cmd)
+    }
+}
+
+impl std::fmt::Display for ProjectCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shell(cmd) => f.write_str(cmd),
+            Self::Detailed(def) => {
+                f.write_str(&def.scripts().join(" → "))?;
+                if let Some(ref cwd) = def.cwd {
+                    write!(f, " (in {})", cwd)?;
+                }
+                if !def.depends.is_empty() {
+                    write!(f, " [after {}]", def.depends.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Shell commands run around service lifecycle events
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Hooks {
+    /// Run after `start` once all services are ready (e.g. migrations, seeds)
+    #[serde(default)]
+    pub post_start: Vec<String>,
+    /// Run before `stop` while services are still up (e.g. dumps)
+    #[serde(default)]
+    pub pre_stop: Vec<String>,
+}
+
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        self.post_start.is_empty() && self.pre_stop.is_empty()
+    }
+}
+
 fn default_project_type() -> String {
     "unknown".to_string()
 }
@@ -32,13 +171,132 @@ impl Project {
             name,
             path,
             project_type,
+            stacks: Vec::new(),
             services: Vec::new(),
             env: HashMap::new(),
             commands: HashMap::new(),
+            hooks: Hooks::default(),
+            packages: HashMap::new(),
+            worktrees: HashMap::new(),
             last_used: Some(Utc::now().to_rfc3339()),
         }
     }
 
+    /// Whether the primary type or any other stack contains `filter`
+    pub fn has_type(&self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        std::iter::once(&self.project_type)
+            .chain(&self.stacks)
+            .any(|t| t.to_lowercase().contains(&filter))
+    }
+
+    /// The primary type, followed by the other stacks in parentheses
+    pub fn type_label(&self) -> String {
+        let others: Vec<&str> = self
+            .stacks
+            .iter()
+            .filter(|t| **t != self.project_type)
+            .map(String::as_str)
+            .collect();
+        if others.is_empty() {
+            self.project_type.clone()
+        } else {
+            format!("{} (+{})", self.project_type, others.join(", "))
+        }
+    }
+
+    /// Name of the registered project this one belongs to (itself, unless
+    /// it is a `project:package` sub-project or a `project@branch` worktree)
+    pub fn root_name(&self) -> &str {
+        self.name.split([':', '@']).next().unwrap_or(&self.name)
+    }
+
+    /// Configured packages plus members of the root's workspace manifests,
+    /// sorted by name. Configured packages win on name clashes.
+    pub fn package_list(&self) -> Vec<(String, Package)> {
+        let mut packages: Vec<(String, Package)> = self
+            .packages
+            .iter()
+            .map(|(name, pkg)| (name.clone(), pkg.clone()))
+            .collect();
+        if self.exists() {
+            for member in workspace::discover(&self.expanded_path()) {
+                let known = packages
+                    .iter()
+                    .any(|(name, pkg)| *name == member.name || pkg.path == member.path);
+                if !known {
+                    let pkg = Package {
+                        path: member.path,
+                        ..Default::default()
+                    };
+                    packages.push((member.name, pkg));
+                }
+            }
+        }
+        packages.sort_by(|a, b| a.0.cmp(&b.0));
+        packages
+    }
+
+    /// The package as a project of its own, named `project:package`, with
+    /// its own type, services and commands. It inherits the root's env.
+    pub fn sub_project(&self, name: &str, package: &Package) -> Project {
+        let path = self.expanded_path().join(&package.path);
+        let stacks = Project::detect_stacks(&path, package.package_type.as_deref());
+        let project_type = stacks
+            .first()
+            .map_or_else(|| "unknown".to_string(), |s| s.project_type.clone());
+        let mut commands: HashMap<String, ProjectCommand> =
+            Project::detect_stack_commands(&path, &stacks)
+                .into_iter()
+                .map(|(name, cmd)| (name, cmd.into()))
+                .collect();
+        commands.extend(package.commands.clone());
+
+        Project {
+            name: format!("{}:{}", self.name, name),
+            services: Project::detect_services(&path),
+            path: path.to_string_lossy().to_string(),
+            project_type,
+            stacks: stack_types(&stacks),
+            env: self.env.clone(),
+            commands,
+            hooks: Hooks::default(),
+            packages: HashMap::new(),
+            worktrees: HashMap::new(),
+            last_used: self.last_used.clone(),
+        }
+    }
+
+    /// All sub-projects of this project
+    pub fn sub_projects(&self) -> Vec<Project> {
+        self.package_list()
+            .iter()
+            .map(|(name, pkg)| self.sub_project(name, pkg))
+            .collect()
+    }
+
+    /// The worktree of `branch` as a project of its own, named
+    /// `project@branch`. It inherits everything but the path.
+    pub fn worktree_project(&self, branch: &str, path: &str) -> Project {
+        Project {
+            name: format!("{}@{}", self.name, branch),
+            path: path.to_string(),
+            worktrees: HashMap::new(),
+            ..self.clone()
+        }
+    }
+
+    /// All registered worktrees of this project, sorted by branch
+    pub fn worktree_projects(&self) -> Vec<Project> {
+        let mut worktrees: Vec<Project> = self
+            .worktrees
+            .iter()
+            .map(|(branch, path)| self.worktree_project(branch, path))
+            .collect();
+        worktrees.sort_by(|a, b| a.name.cmp(&b.name));
+        worktrees
+    }
+
     /// Get the expanded absolute path
     pub fn expanded_path(&self) -> PathBuf {
         ConfigManager::expand_path(&self.path)
@@ -62,30 +320,7 @@ impl Project {
     /// Get a human-readable "time ago" string
     pub fn last_used_ago(&self) -> String {
         match self.last_used_time() {
-            Some(dt) => {
-                let duration = Utc::now().signed_duration_since(dt);
-                if duration.num_minutes() < 1 {
-                    "just now".to_string()
-                } else if duration.num_minutes() < 60 {
-                    format!("{} min ago", duration.num_minutes())
-                } else if duration.num_hours() < 24 {
-                    let h = duration.num_hours();
-                    format!("{} hour{} ago", h, if h == 1 { "" } else { "s" })
-                } else if duration.num_days() < 7 {
-                    let d = duration.num_days();
-                    format!("{} day{} ago", d, if d == 1 { "" } else { "s" })
-                } else if duration.num_weeks() < 4 {
-                    let w = duration.num_weeks();
-                    format!("{} week{} ago", w, if w == 1 { "" } else { "s" })
-                } else {
-                    let m = duration.num_days() / 30;
-                    if m < 1 {
-                        "1 month ago".to_string()
-                    } else {
-                        format!("{} month{} ago", m, if m == 1 { "" } else { "s" })
-                    }
-                }
-            }
+            Some(dt) => display::time_ago(dt),
             None => "never".to_string(),
         }
     }
@@ -125,76 +360,71 @@ impl Project {
         path.join(".nvmrc").exists() || path.join(".node-version").exists()
     }
 
-    /// Detect project type from files in the directory
+    /// Detect project type from files in the directory, using the rules
+    /// in `detect` (built-in plus ~/.projectctl/detectors.toml)
     pub fn detect_type(path: &Path) -> String {
-        if path.join("Cargo.toml").exists() {
-            if path.join("src-tauri").exists() {
-                return "tauri".to_string();
-            }
-            return "rust".to_string();
-        }
-        if path.join("pyproject.toml").exists() || path.join("setup.py").exists() {
-            if path.join("requirements.txt").exists() || path.join("pyproject.toml").exists() {
-                // Check for FastAPI
-                if let Ok(content) = std::fs::read_to_string(path.join("requirements.txt")) {
-                    if content.to_lowercase().contains("fastapi") {
-                        return "fastapi".to_string();
-                    }
-                    if content.to_lowercase().contains("django") {
-                        return "django".to_string();
-                    }
-                    if content.to_lowercase().contains("flask") {
-                        return "flask".to_string();
-                    }
-                }
-                if let Ok(content) = std::fs::read_to_string(path.join("pyproject.toml")) {
-                    if content.to_lowercase().contains("fastapi") {
-                        return "fastapi".to_string();
-                    }
-                    if content.to_lowercase().contains("django") {
-                        return "django".to_string();
-                    }
-                    if content.to_lowercase().contains("flask") {
-                        return "flask".to_string();
-                    }
-                }
-            }
-            return "python".to_string();
+        detect::detect(path)
+    }
+
+    /// Detect the stacks of a directory. An explicitly given type replaces
+    /// the detected stack it belongs to and becomes the primary one.
+    pub fn detect_stacks(path: &Path, explicit_type: Option<&str>) -> Vec<DetectedStack> {
+        let mut stacks = detect::detect_stacks(path);
+        if let Some(project_type) = explicit_type {
+            let name = detect::detectors()
+                .iter()
+                .find(|d| d.project_type == project_type)
+                .map_or(project_type, |d| d.stack_name())
+                .to_string();
+            stacks.retain(|s| s.name != name);
+            stacks.insert(
+                0,
+                DetectedStack {
+                    name,
+                    project_type: project_type.to_string(),
+                },
+            );
         }
-        if path.join("package.json").exists() {
-            if let Ok(content) = std::fs::read_to_string(path.join("package.json")) {
-                let lower = content.to_lowercase();
-                if lower.contains("\"next\"") {
-                    return "nextjs".to_string();
-                }
-                if lower.contains("\"nuxt\"") {
-                    return "nuxt".to_string();
-                }
-                if lower.contains("\"react\"") {
-                    if lower.contains("\"vite\"") {
-                        return "react-vite".to_string();
-                    }
-                    return "react".to_string();
-                }
-                if lower.contains("\"vue\"") {
-                    return "vue".to_string();
-                }
-                if lower.contains("\"svelte\"") {
-                    return "svelte".to_string();
-                }
-                if lower.contains("\"express\"") {
-                    return "express".to_string();
+        stacks
+    }
+
+    /// Sub-directories with a recognizable project type, for monorepos
+    /// whose root has no workspace manifest (e.g. `backend/` + `frontend/`)
+    pub fn detect_packages(path: &Path) -> HashMap<String, Package> {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return HashMap::new();
+        };
+        entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .filter_map(|p| {
+                let name = p.file_name()?.to_string_lossy().to_string();
+                if name.starts_with('.') || name == "node_modules" || name == "target" {
+                    return None;
                 }
-            }
-            return "node".to_string();
-        }
-        if path.join("go.mod").exists() {
-            return "go".to_string();
-        }
-        if path.join("pom.xml").exists() || path.join("build.gradle").exists() {
-            return "java".to_string();
+                let detected = Project::detect_type(&p) != "unknown"
+                    || !Project::detect_services(&p).is_empty();
+                detected.then(|| {
+                    let pkg = Package {
+                        path: name.clone(),
+                        ..Default::default()
+                    };
+                    (name, pkg)
+                })
+            })
+            .collect()
+    }
+
+    /// Packages to register with a new project of `project_type`. Workspace
+    /// members are discovered on the fly, so only an untyped root without a
+    /// workspace manifest gets its recognizable sub-directories.
+    pub fn initial_packages(path: &Path, project_type: &str) -> HashMap<String, Package> {
+        if project_type == "unknown" && workspace::discover(path).is_empty() {
+            Self::detect_packages(path)
+        } else {
+            HashMap::new()
         }
-        "unknown".to_string()
     }
 
     /// Detect services from docker-compose.yml
@@ -218,62 +448,169 @@ impl Project {
 
     /// Detect common commands based on project type
     pub fn detect_commands(path: &Path, project_type: &str) -> HashMap<String, String> {
-        let mut commands = HashMap::new();
-        match project_type {
-            "rust" => {
-                commands.insert("dev".to_string(), "cargo run".to_string());
-                commands.insert("test".to_string(), "cargo test".to_string());
-                commands.insert("build".to_string(), "cargo build --release".to_string());
+        let mut commands = guess_commands(path, project_type);
+
+        // Prefer what the project's own task runners define over the guesses
+        if path.join("package.json").exists() {
+            // Guessed npm commands only make sense for scripts that exist
+            commands.retain(|_, cmd| !cmd.starts_with("npm "));
+        }
+        let mut from_tasks = HashSet::new();
+        for task in tasks::discover(path) {
+            let standard = STANDARD_COMMANDS.contains(&task.name.as_str());
+            if standard && from_tasks.insert(task.name.clone()) {
+                commands.insert(task.name, task.command);
             }
-            "fastapi" | "python" => {
-                if path.join("manage.py").exists() {
-                    commands.insert("dev".to_string(), "python manage.py runserver".to_string());
-                    commands.insert("test".to_string(), "python manage.py test".to_string());
-                } else {
-                    commands.insert("dev".to_string(), "uvicorn app.main:app --reload".to_string());
-                    commands.insert("test".to_string(), "pytest".to_string());
+        }
+        commands
+    }
+
+    /// Commands of a polyglot project: those of the primary stack, plus each
+    /// stack's own set namespaced with its name (`web:dev`, `api:test`)
+    pub fn detect_stack_commands(path: &Path, stacks: &[DetectedStack]) -> HashMap<String, String> {
+        let primary = stacks.first().map_or("unknown", |s| s.project_type.as_str());
+        let mut commands = Project::detect_commands(path, primary);
+        if stacks.len() < 2 {
+            return commands;
+        }
+
+        let scripts: Vec<tasks::DetectedTask> = tasks::discover(path)
+            .into_iter()
+            .filter(|t| t.source == "package.json")
+            .collect();
+        for stack in stacks {
+            let mut stack_commands = guess_commands(path, &stack.project_type);
+            // npm guesses become the package.json scripts that really exist
+            if stack_commands.values().any(|cmd| cmd.starts_with("npm ")) {
+                stack_commands.retain(|_, cmd| !cmd.starts_with("npm "));
+                for task in &scripts {
+                    if STANDARD_COMMANDS.contains(&task.name.as_str()) {
+                        stack_commands.insert(task.name.clone(), task.command.clone());
+                    }
                 }
             }
-            "django" => {
+            for (name, cmd) in stack_commands {
+                commands.insert(format!("{}:{}", stack.name, name), cmd);
+            }
+        }
+        commands
+    }
+}
+
+/// Guessed commands of a project type
+fn guess_commands(path: &Path, project_type: &str) -> HashMap<String, String> {
+    let mut commands = HashMap::new();
+    match project_type {
+        "rust" => {
+            commands.insert("dev".to_string(), "cargo run".to_string());
+            commands.insert("test".to_string(), "cargo test".to_string());
+            commands.insert("build".to_string(), "cargo build --release".to_string());
+        }
+        "fastapi" | "python" => {
+            if path.join("manage.py").exists() {
                 commands.insert("dev".to_string(), "python manage.py runserver".to_string());
                 commands.insert("test".to_string(), "python manage.py test".to_string());
+            } else {
+                let app = asgi_app(path);
+                commands.insert("dev".to_string(), format!("uvicorn {} --reload", app));
+                commands.insert("test".to_string(), "pytest".to_string());
             }
-            "nextjs" => {
-                commands.insert("dev".to_string(), "npm run dev".to_string());
-                commands.insert("build".to_string(), "npm run build".to_string());
-                commands.insert("test".to_string(), "npm test".to_string());
-            }
-            "react-vite" | "react" | "vue" | "svelte" => {
-                commands.insert("dev".to_string(), "npm run dev".to_string());
-                commands.insert("build".to_string(), "npm run build".to_string());
-                commands.insert("test".to_string(), "npm test".to_string());
-            }
-            "node" | "express" => {
-                commands.insert("dev".to_string(), "npm run dev".to_string());
-                commands.insert("start".to_string(), "npm start".to_string());
-                commands.insert("test".to_string(), "npm test".to_string());
-            }
-            "tauri" => {
-                commands.insert("dev".to_string(), "cargo tauri dev".to_string());
-                commands.insert("build".to_string(), "cargo tauri build".to_string());
-                commands.insert("test".to_string(), "cargo test".to_string());
-            }
-            "go" => {
-                commands.insert("dev".to_string(), "go run .".to_string());
-                commands.insert("test".to_string(), "go test ./...".to_string());
-                commands.insert("build".to_string(), "go build -o bin/app .".to_string());
-            }
-            _ => {}
         }
-        commands
+        "django" => {
+            commands.insert("dev".to_string(), "python manage.py runserver".to_string());
+            commands.insert("test".to_string(), "python manage.py test".to_string());
+        }
+        "nextjs" => {
+            commands.insert("dev".to_string(), "npm run dev".to_string());
+            commands.insert("build".to_string(), "npm run build".to_string());
+            commands.insert("test".to_string(), "npm test".to_string());
+        }
+        "react-vite" | "react" | "vue" | "svelte" | "sveltekit" | "astro" | "remix" | "nuxt" => {
+            commands.insert("dev".to_string(), "npm run dev".to_string());
+            commands.insert("build".to_string(), "npm run build".to_string());
+            commands.insert("test".to_string(), "npm test".to_string());
+        }
+        "node" | "express" => {
+            commands.insert("dev".to_string(), "npm run dev".to_string());
+            commands.insert("start".to_string(), "npm start".to_string());
+            commands.insert("test".to_string(), "npm test".to_string());
+        }
+        "tauri" => {
+            commands.insert("dev".to_string(), "cargo tauri dev".to_string());
+            commands.insert("build".to_string(), "cargo tauri build".to_string());
+            commands.insert("test".to_string(), "cargo test".to_string());
+        }
+        "flutter" => {
+            commands.insert("dev".to_string(), "flutter run".to_string());
+            commands.insert("test".to_string(), "flutter test".to_string());
+            commands.insert("build".to_string(), "flutter build".to_string());
+        }
+        "dart" => {
+            commands.insert("dev".to_string(), "dart run".to_string());
+            commands.insert("test".to_string(), "dart test".to_string());
+        }
+        "phoenix" => {
+            commands.insert("dev".to_string(), "mix phx.server".to_string());
+            commands.insert("test".to_string(), "mix test".to_string());
+        }
+        "elixir" => {
+            commands.insert("dev".to_string(), "iex -S mix".to_string());
+            commands.insert("test".to_string(), "mix test".to_string());
+            commands.insert("build".to_string(), "mix compile".to_string());
+        }
+        "dotnet" => {
+            commands.insert("dev".to_string(), "dotnet watch run".to_string());
+            commands.insert("test".to_string(), "dotnet test".to_string());
+            commands.insert("build".to_string(), "dotnet build".to_string());
+        }
+        "go" => {
+            commands.insert("dev".to_string(), "go run .".to_string());
+            commands.insert("test".to_string(), "go test ./...".to_string());
+            commands.insert("build".to_string(), "go build -o bin/app .".to_string());
+        }
+        _ => {}
     }
+    commands
+}
+
+/// Types of the detected stacks, kept only for polyglot projects
+pub fn stack_types(stacks: &[DetectedStack]) -> Vec<String> {
+    if stacks.len() < 2 {
+        return Vec::new();
+    }
+    stacks.iter().map(|s| s.project_type.clone()).collect()
+}
+
+/// Command names taken from the project's task runners when registering it
+const STANDARD_COMMANDS: &[&str] = &[
+    "dev", "start", "serve", "test", "build", "lint", "format", "check", "typecheck",
+];
+
+/// The `module:app` target for uvicorn, based on where the entrypoint lives
+fn asgi_app(path: &Path) -> &'static str {
+    let candidates = [
+        ("app/main.py", "app.main:app"),
+        ("src/main.py", "src.main:app"),
+        ("main.py", "main:app"),
+        ("app.py", "app:app"),
+    ];
+    candidates
+        .iter()
+        .find(|(file, _)| path.join(file).exists())
+        .map_or("app.main:app", |(_, app)| app)
 }
 
 /// Simple docker-compose service parser
 fn parse_compose_services(content: &str) -> Vec<String> {
-    let mut services = Vec::new();
-    let mut in_services = false;
-    let mut service_indent: Option<usize> = None;
+    parse_yaml_section_keys(content, "services")
+}
+
+/// Keys directly under a top-level YAML mapping, e.g. the service names
+/// under `services:` or the task names under `tasks:`
+pub fn parse_yaml_section_keys(content: &str, section: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut in_section = false;
+    let mut key_indent: Option<usize> = None;
 
     for line in content.lines() {
         let trimmed = line.trim();
@@ -281,34 +618,45 @@ fn parse_compose_services(content: &str) -> Vec<String> {
             continue;
         }
 
-        // Detect the "services:" key
-        if trimmed == "services:" {
-            in_services = true;
-            service_indent = None;
+        let indent = line.len() - line.trim_start().len();
+
+        // Detect the section key
+        if indent == 0 {
+            if in_section {
+                // Back to top level
+                break;
+            }
+            in_section = yaml_key(trimmed) == Some(section);
+            key_indent = None;
             continue;
         }
 
-        if in_services {
-            let indent = line.len() - line.trim_start().len();
-            if let Some(si) = service_indent {
-                if indent <= 0 && !trimmed.is_empty() {
-                    // Back to top level
-                    break;
-                }
-                if indent == si && trimmed.ends_with(':') {
-                    let name = trimmed.trim_end_matches(':').trim();
-                    if !name.is_empty() {
-                        services.push(name.to_string());
-                    }
-                }
-            } else if trimmed.ends_with(':') && indent > 0 {
-                service_indent = Some(indent);
-                let name = trimmed.trim_end_matches(':').trim();
-                if !name.is_empty() {
-                    services.push(name.to_string());
+        if in_section {
+            if key_indent.is_none() {
+                key_indent = Some(indent);
+            }
+            if Some(indent) == key_indent {
+                if let Some(key) = yaml_key(trimmed) {
+                    keys.push(key.to_string());
                 }
             }
         }
     }
-    services
+    keys
+}
+
+/// The key of a `key:` or `key: value` YAML line
+fn yaml_key(line: &str) -> Option<&str> {
+    if line.starts_with('-') {
+        return None;
+    }
+    let (key, rest) = line.split_once(':')?;
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+    if key.is_empty() || key.contains(' ') {
+        return None;
+    }
+    Some(key)
 }
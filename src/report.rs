@@ -0,0 +1,140 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use std::collections::BTreeSet;
+
+use crate::activity::{Activity, ActivityKind};
+use crate::git;
+use crate::history::RunRecord;
+use crate::probe;
+use crate::project::Project;
+
+/// Activity of one project over the report period
+#[derive(Debug, Default)]
+pub struct ProjectReport {
+    pub project: String,
+    pub commits: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Local dates with commits, runs, switches or starts
+    pub active_days: BTreeSet<NaiveDate>,
+    pub runs: usize,
+    pub run_ms: u64,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+impl ProjectReport {
+    fn is_empty(&self) -> bool {
+        self.commits == 0 && self.runs == 0 && self.active_days.is_empty()
+    }
+}
+
+/// Parse `--since`: a period back from now (`7d`, `2w`, `12h`) or a
+/// local date (`2024-05-01`)
+pub fn parse_since(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let start = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+        return Local
+            .from_local_datetime(&start)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+            .context("Invalid local date");
+    }
+
+    let (amount, unit) = value.split_at(value.len().saturating_sub(1));
+    let Ok(amount) = amount.parse::<i64>() else {
+        bail!("Invalid period '{}'. Use e.g. 7d, 2w, 12h or a date like 2024-05-01", value);
+    };
+    let period = match unit {
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        "w" => Duration::weeks(amount),
+        _ => bail!("Invalid period '{}'. Use e.g. 7d, 2w, 12h or a date like 2024-05-01", value),
+    };
+    Ok(Utc::now() - period)
+}
+
+/// Commits, runs, switches and starts per project since `since`. Commits
+/// are those of `author`, or of each repository's configured `user.email`.
+/// Projects without any activity are left out.
+pub fn build(
+    projects: &[Project],
+    records: &[RunRecord],
+    events: &[Activity],
+    since: DateTime<Utc>,
+    author: Option<&str>,
+) -> Vec<ProjectReport> {
+    let activity = probe::run_parallel(projects, |p| {
+        let path = p.expanded_path();
+        if !path.join(".git").exists() {
+            return None;
+        }
+        let email = author.map(String::from).or_else(|| git::user_email(&path))?;
+        git::commit_activity(&path, &email, since).ok()
+    });
+
+    let mut reports: Vec<ProjectReport> = projects
+        .iter()
+        .zip(activity)
+        .map(|(project, commits)| {
+            let commits = commits.unwrap_or_default();
+            let mut report = ProjectReport {
+                project: project.name.clone(),
+                commits: commits.commits,
+                insertions: commits.insertions,
+                deletions: commits.deletions,
+                active_days: commits.days,
+                last_used: project.last_used_time(),
+                ..Default::default()
+            };
+            // Runs, switches and starts of packages and worktrees count for
+            // their project
+            let owns = |name: &str| name.split([':', '@']).next() == Some(project.name.as_str());
+            for record in records.iter().filter(|r| r.started >= since && owns(&r.project)) {
+                report.runs += 1;
+                report.run_ms += record.duration_ms;
+                report.active_days.insert(local_date(record.started));
+            }
+            for event in events.iter().filter(|e| e.time >= since && owns(&e.project)) {
+                if matches!(event.kind, ActivityKind::Switch | ActivityKind::Start) {
+                    report.active_days.insert(local_date(event.time));
+                }
+            }
+            // Switches from before the activity log only left `last_used`
+            if let Some(used) = report.last_used.filter(|t| *t >= since) {
+                report.active_days.insert(local_date(used));
+            }
+            report
+        })
+        .filter(|r| !r.is_empty())
+        .collect();
+
+    reports.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then(b.active_days.len().cmp(&a.active_days.len()))
+            .then(a.project.cmp(&b.project))
+    });
+    reports
+}
+
+/// Sum of all project reports; active days are merged, not added
+pub fn total(reports: &[ProjectReport]) -> ProjectReport {
+    let mut total = ProjectReport {
+        project: "Total".to_string(),
+        ..Default::default()
+    };
+    for report in reports {
+        total.commits += report.commits;
+        total.insertions += report.insertions;
+        total.deletions += report.deletions;
+        total.runs += report.runs;
+        total.run_ms += report.run_ms;
+        total.active_days.extend(report.active_days.iter().copied());
+        total.last_used = total.last_used.max(report.last_used);
+    }
+    total
+}
+
+fn local_date(time: DateTime<Utc>) -> NaiveDate {
+    time.with_timezone(&Local).date_naive()
+}
@@ -0,0 +1,265 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use crate::env::CommandEnv;
+use crate::project::{Project, ProjectCommand};
+
+/// Order in which commands must run so that every command's `depends`
+/// come before it. Fails on unknown commands and dependency cycles.
+pub fn resolve_order(
+    commands: &HashMap<String, ProjectCommand>,
+    target: &str,
+) -> Result<Vec<String>> {
+    fn visit(
+        commands: &HashMap<String, ProjectCommand>,
+        name: &str,
+        path: &mut Vec<String>,
+        order: &mut Vec<String>,
+    ) -> Result<()> {
+        if order.iter().any(|n| n == name) {
+            return Ok(());
+        }
+        if let Some(pos) = path.iter().position(|n| n == name) {
+            let mut cycle = path[pos..].to_vec();
+            cycle.push(name.to_string());
+            bail!("Command dependency cycle: {}", cycle.join(" → "));
+        }
+        let command = commands.get(name).ok_or_else(|| match path.last() {
+            Some(parent) => {
+                anyhow::anyhow!("Command '{}' depends on unknown command '{}'", parent, name)
+            }
+            None => anyhow::anyhow!("Command '{}' not found", name),
+        })?;
+
+        path.push(name.to_string());
+        for dep in &command.def().depends {
+            visit(commands, dep, path, order)?;
+        }
+        path.pop();
+        order.push(name.to_string());
+        Ok(())
+    }
+
+    let mut order = Vec::new();
+    visit(commands, target, &mut Vec::new(), &mut order)?;
+    Ok(order)
+}
+
+/// Quote an argument for `sh` if it contains anything but safe characters
+pub fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,@%+".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Expand `{args}`, `{project}` and `{project_path}` in a script, in a
+/// single pass so substituted values are never expanded again. All values
+/// are shell-quoted.
+pub fn expand_placeholders(script: &str, project: &Project, args: &[String]) -> String {
+    let quoted: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
+    let placeholders = [
+        ("{args}", quoted.join(" ")),
+        ("{project}", shell_quote(&project.name)),
+        ("{project_path}", shell_quote(&project.expanded_path().to_string_lossy())),
+    ];
+
+    let mut expanded = String::with_capacity(script.len());
+    let mut rest = script;
+    while let Some(pos) = rest.find('{') {
+        expanded.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        match placeholders.iter().find(|(key, _)| rest.starts_with(key)) {
+            Some((key, value)) => {
+                expanded.push_str(value);
+                rest = &rest[key.len()..];
+            }
+            None => {
+                expanded.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Final scripts of a command with placeholders expanded. Trailing `args`
+/// are appended to the last script unless a script uses `{args}`.
+pub fn command_scripts(
+    command: &ProjectCommand,
+    project: &Project,
+    args: &[String],
+) -> Vec<String> {
+    let scripts = command.def().scripts();
+    let uses_args = scripts.iter().any(|s| s.contains("{args}"));
+    let mut scripts: Vec<String> = scripts
+        .iter()
+        .map(|s| expand_placeholders(s, project, args))
+        .collect();
+    if !uses_args && !args.is_empty() {
+        if let Some(last) = scripts.last_mut() {
+            let quoted: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
+            last.push(' ');
+            last.push_str(&quoted.join(" "));
+        }
+    }
+    scripts
+}
+
+/// Run a project command and its dependencies in the foreground.
+/// `args` are forwarded to the requested command only. Stops at the first
+/// failing script and returns its exit code (0 when everything succeeded).
+pub fn run_command(project: &Project, name: &str, args: &[String]) -> Result<i32> {
+    let order = resolve_order(&project.commands, name)?;
+
+    for cmd_name in &order {
+        let command = &project.commands[cmd_name];
+        let def = command.def();
+        let cmd_args: &[String] = if cmd_name == name { args } else { &[] };
+        let scripts = command_scripts(command, project, cmd_args);
+        if scripts.is_empty() {
+            continue;
+        }
+
+        let run_env = CommandEnv::build(project, Some(&def))?;
+        println!(
+            "Running: {} {}\n",
+            project.name.cyan().bold(),
+            cmd_name.bold()
+        );
+
+        for script in &scripts {
+            println!("Executing: {}\n", script.dimmed());
+            let status = run_env
+                .shell(script)
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit())
+                .stdin(Stdio::inherit())
+                .status()
+                .with_context(|| format!("Failed to run: {}", script))?;
+
+            if !status.success() {
+                // Killed by a signal: report like the shell does
+                return Ok(status.code().unwrap_or(128));
+            }
+        }
+        if order.len() > 1 {
+            println!();
+        }
+    }
+
+    Ok(0)
+}
+
+/// What to run in each project of a batch
+pub enum BatchJob<'a> {
+    /// A project command (configured or discovered), with its dependencies
+    Command { name: &'a str, args: &'a [String] },
+    /// An arbitrary shell string, run in the project root
+    Shell(&'a str),
+}
+
+/// Outcome of a batch job in one project
+#[derive(Debug)]
+pub struct BatchResult {
+    pub project: String,
+    /// None when the job could not be started
+    pub exit_code: Option<i32>,
+    pub duration: Duration,
+    pub log: PathBuf,
+    pub error: Option<String>,
+}
+
+impl BatchResult {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Run a job in every project in parallel (at most `jobs` at a time),
+/// writing each project's output to `<log_dir>/<project>.log` (with `/`,
+/// `:` and `@` in the name replaced by `-`)
+pub fn run_batch(
+    projects: &[Project],
+    job: &BatchJob,
+    jobs: usize,
+    log_dir: &Path,
+) -> Result<Vec<BatchResult>> {
+    std::fs::create_dir_all(log_dir).context("Failed to create log directory")?;
+
+    Ok(crate::probe::run_parallel_limit(projects, jobs, |project| {
+        let log = log_dir.join(format!("{}.log", project.name.replace(['/', ':', '@'], "-")));
+        let started = Instant::now();
+        let outcome = run_job_logged(project, job, &log);
+        let result = BatchResult {
+            project: project.name.clone(),
+            exit_code: outcome.as_ref().ok().copied(),
+            duration: started.elapsed(),
+            log,
+            error: outcome.err().map(|e| e.to_string()),
+        };
+
+        let icon = if result.success() {
+            "✓".green()
+        } else {
+            "✗".red()
+        };
+        println!(
+            "  {} {} {}",
+            icon,
+            result.project,
+            format!("({:.1}s)", result.duration.as_secs_f32()).dimmed()
+        );
+        result
+    }))
+}
+
+/// Run a job with stdout and stderr appended to `log`; returns the exit code
+fn run_job_logged(project: &Project, job: &BatchJob, log: &Path) -> Result<i32> {
+    let mut file = File::create(log).context("Failed to create log file")?;
+
+    let steps: Vec<(Option<crate::project::CommandDef>, String)> = match job {
+        BatchJob::Shell(script) => vec![(None, script.to_string())],
+        BatchJob::Command { name, args } => {
+            let order = resolve_order(&project.commands, name)?;
+            let mut steps = Vec::new();
+            for cmd_name in &order {
+                let command = &project.commands[cmd_name];
+                let cmd_args: &[String] = if cmd_name == name { args } else { &[] };
+                for script in command_scripts(command, project, cmd_args) {
+                    steps.push((Some(command.def()), script));
+                }
+            }
+            steps
+        }
+    };
+
+    for (def, script) in &steps {
+        let run_env = CommandEnv::build(project, def.as_ref())?;
+        writeln!(file, "$ {}", script)?;
+        let status = run_env
+            .shell(script)
+            .stdin(Stdio::null())
+            .stdout(Stdio::from(file.try_clone()?))
+            .stderr(Stdio::from(file.try_clone()?))
+            .status()
+            .with_context(|| format!("Failed to run: {}", script))?;
+        if !status.success() {
+            // Killed by a signal: report like the shell does
+            return Ok(status.code().unwrap_or(128));
+        }
+    }
+    Ok(0)
+}
@@ -1,8 +1,13 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, Instant};
 
+use crate::display;
+use crate::env::CommandEnv;
 use crate::project::Project;
 
 /// Check if docker/docker compose is available
@@ -31,15 +36,225 @@ fn find_compose_file(project_path: &Path) -> Option<String> {
     None
 }
 
+/// Lifecycle state of a compose container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ServiceState {
+    Running,
+    Restarting,
+    Paused,
+    Created,
+    Exited,
+    Dead,
+    Removing,
+    Unknown(String),
+}
+
+impl ServiceState {
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "running" => Self::Running,
+            "restarting" => Self::Restarting,
+            "paused" => Self::Paused,
+            "created" => Self::Created,
+            "exited" => Self::Exited,
+            "dead" => Self::Dead,
+            "removing" => Self::Removing,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Running => "running",
+            Self::Restarting => "restarting",
+            Self::Paused => "paused",
+            Self::Created => "created",
+            Self::Exited => "exited",
+            Self::Dead => "dead",
+            Self::Removing => "removing",
+            Self::Unknown(s) => s,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        *self == Self::Running
+    }
+}
+
+impl std::fmt::Display for ServiceState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Container ports commonly serving HTTP, shown as localhost links
+const WEB_PORTS: &[u16] = &[
+    80, 443, 3000, 3001, 4000, 4200, 4321, 5000, 5173, 5174, 8000, 8001, 8080, 8081, 8443,
+    8888, 9000,
+];
+
+/// A single port mapping of a compose container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortMapping {
+    pub host_port: Option<u16>,
+    pub container_port: u16,
+    pub protocol: String,
+}
+
+impl PortMapping {
+    /// Parse one entry of the docker `Ports` column, e.g.
+    /// `0.0.0.0:8000->8000/tcp` or `5432/tcp`
+    fn parse(entry: &str) -> Option<Self> {
+        let entry = entry.trim();
+        let (mapping, protocol) = entry.rsplit_once('/').unwrap_or((entry, "tcp"));
+        let (host_port, container_port) = match mapping.split_once("->") {
+            Some((host, container)) => {
+                let host_port = host.rsplit_once(':').map_or(host, |(_, p)| p);
+                (host_port.split('-').next().and_then(|p| p.parse().ok()), container)
+            }
+            None => (None, mapping),
+        };
+        // Ranges like 8000-8002 are reported as-is; keep the first port
+        let container_port = container_port.split('-').next()?.parse().ok()?;
+        Some(Self {
+            host_port,
+            container_port,
+            protocol: protocol.to_string(),
+        })
+    }
+
+    /// Whether this port is published on the host and likely speaks HTTP
+    pub fn is_web(&self) -> bool {
+        self.host_port.is_some()
+            && self.protocol == "tcp"
+            && WEB_PORTS.contains(&self.container_port)
+    }
+
+    /// Browser URL for a published web port
+    pub fn url(&self) -> Option<String> {
+        if !self.is_web() {
+            return None;
+        }
+        let host_port = self.host_port?;
+        let scheme = if self.container_port == 443 || self.container_port == 8443 {
+            "https"
+        } else {
+            "http"
+        };
+        Some(format!("{}://localhost:{}", scheme, host_port))
+    }
+}
+
+impl std::fmt::Display for PortMapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.host_port {
+            Some(host) if host == self.container_port => write!(f, "{}", host),
+            Some(host) => write!(f, "{}->{}", host, self.container_port),
+            None => write!(f, "{}/{}", self.container_port, self.protocol),
+        }
+    }
+}
+
+/// Status of a single docker compose service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceStatus {
+    pub name: String,
+    pub container: String,
+    pub state: ServiceState,
+    pub health: Option<String>,
+    pub exit_code: Option<i32>,
+    pub uptime: String,
+    pub image: String,
+    pub ports: Vec<PortMapping>,
+}
+
+impl ServiceStatus {
+    fn from_json(value: &serde_json::Value) -> Self {
+        let name = value["Service"]
+            .as_str()
+            .or_else(|| value["Name"].as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let container = value["Name"].as_str().unwrap_or("").to_string();
+        let state = ServiceState::parse(value["State"].as_str().unwrap_or("unknown"));
+        let health = value["Health"]
+            .as_str()
+            .filter(|h| !h.is_empty())
+            .map(|h| h.to_string());
+        let exit_code = value["ExitCode"].as_i64().map(|c| c as i32);
+        let uptime = value["RunningFor"]
+            .as_str()
+            .or_else(|| value["Status"].as_str())
+            .unwrap_or("")
+            .to_string();
+        let image = value["Image"].as_str().unwrap_or("").to_string();
+
+        let mut ports: Vec<PortMapping> = Vec::new();
+        if let Some(publishers) = value["Publishers"].as_array() {
+            for publisher in publishers {
+                let Some(container_port) = publisher["TargetPort"].as_u64() else {
+                    continue;
+                };
+                let host_port = publisher["PublishedPort"]
+                    .as_u64()
+                    .filter(|p| *p != 0)
+                    .map(|p| p as u16);
+                ports.push(PortMapping {
+                    host_port,
+                    container_port: container_port as u16,
+                    protocol: publisher["Protocol"].as_str().unwrap_or("tcp").to_string(),
+                });
+            }
+        } else if let Some(raw) = value["Ports"].as_str() {
+            ports.extend(raw.split(',').filter_map(PortMapping::parse));
+        }
+        // IPv4 and IPv6 bindings show up as separate entries
+        let mut seen = Vec::new();
+        ports.retain(|p| {
+            if seen.contains(p) {
+                false
+            } else {
+                seen.push(p.clone());
+                true
+            }
+        });
+
+        Self {
+            name,
+            container,
+            state,
+            health,
+            exit_code,
+            uptime,
+            image,
+            ports,
+        }
+    }
+
+    /// Localhost URLs for published web ports
+    pub fn urls(&self) -> Vec<String> {
+        self.ports.iter().filter_map(|p| p.url()).collect()
+    }
+}
+
 /// Get the status of docker compose services
-pub fn get_compose_status(project_path: &Path) -> Result<Vec<(String, String, String)>> {
+pub fn get_compose_status(project_path: &Path) -> Result<Vec<ServiceStatus>> {
+    compose_ps(project_path, false)
+}
+
+/// Run `docker compose ps`, including stopped containers when `all` is set
+fn compose_ps(project_path: &Path, all: bool) -> Result<Vec<ServiceStatus>> {
     let compose_file = match find_compose_file(project_path) {
         Some(f) => f,
         None => return Ok(Vec::new()),
     };
 
-    let output = Command::new("docker")
-        .args(["compose", "-f", &compose_file, "ps", "--format", "json"])
+    let mut cmd = Command::new("docker");
+    cmd.args(["compose", "-f", &compose_file, "ps", "--format", "json"]);
+    if all {
+        cmd.arg("--all");
+    }
+    let output = cmd
         .current_dir(project_path)
         .output()
         .context("Failed to run docker compose ps")?;
@@ -51,34 +266,70 @@ pub fn get_compose_status(project_path: &Path) -> Result<Vec<(String, String, St
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut services = Vec::new();
 
+    // Older compose versions print a single JSON array, newer ones one object per line
+    if let Ok(serde_json::Value::Array(items)) = serde_json::from_str(stdout.trim()) {
+        services.extend(items.iter().map(ServiceStatus::from_json));
+        return Ok(services);
+    }
+
     for line in stdout.lines() {
         let line = line.trim();
         if line.is_empty() {
             continue;
         }
         if let Ok(value) = serde_json::from_str::<serde_json::Value>(line) {
-            let name = value["Service"]
-                .as_str()
-                .or_else(|| value["Name"].as_str())
-                .unwrap_or("unknown")
-                .to_string();
-            let state = value["State"]
-                .as_str()
-                .unwrap_or("unknown")
-                .to_string();
-            let ports = value["Ports"]
-                .as_str()
-                .unwrap_or("")
-                .to_string();
-            services.push((name, state, ports));
+            services.push(ServiceStatus::from_json(&value));
         }
     }
 
     Ok(services)
 }
 
-/// Start docker compose services
-pub fn start_services(project: &Project, service: Option<&str>) -> Result<()> {
+/// Resource usage of a running container, as reported by `docker stats`
+#[derive(Debug, Clone)]
+pub struct ContainerStats {
+    pub cpu: String,
+    pub memory: String,
+}
+
+/// Get CPU and memory usage of all running containers, keyed by container name
+pub fn get_container_stats() -> Result<HashMap<String, ContainerStats>> {
+    let output = Command::new("docker")
+        .args(["stats", "--no-stream", "--format", "{{json .}}"])
+        .output()
+        .context("Failed to run docker stats")?;
+
+    let mut stats = HashMap::new();
+    if !output.status.success() {
+        return Ok(stats);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+            continue;
+        };
+        let Some(name) = value["Name"].as_str() else {
+            continue;
+        };
+        stats.insert(
+            name.to_string(),
+            ContainerStats {
+                cpu: value["CPUPerc"].as_str().unwrap_or("-").to_string(),
+                memory: value["MemUsage"]
+                    .as_str()
+                    .and_then(|m| m.split('/').next())
+                    .unwrap_or("-")
+                    .trim()
+                    .to_string(),
+            },
+        );
+    }
+    Ok(stats)
+}
+
+/// Start docker compose services, then run the project's `post_start` hooks
+pub fn start_services(project: &Project, service: Option<&str>, hooks: bool) -> Result<()> {
     let project_path = project.expanded_path();
     let compose_file = match find_compose_file(&project_path) {
         Some(f) => f,
@@ -109,19 +360,7 @@ pub fn start_services(project: &Project, service: Option<&str>) -> Result<()> {
         let services = get_compose_status(&project_path)?;
         if !services.is_empty() {
             println!("  Docker Compose:");
-            for (name, state, ports) in &services {
-                let icon = if state == "running" {
-                    "✓".green().to_string()
-                } else {
-                    "✗".red().to_string()
-                };
-                let port_info = if ports.is_empty() {
-                    String::new()
-                } else {
-                    format!(" ({})", ports)
-                };
-                println!("   {} {} {}{}", icon, name, state, port_info);
-            }
+            display::display_services(&services);
         }
         println!("\n{}", "Services started!".green().bold());
     } else {
@@ -129,11 +368,22 @@ pub fn start_services(project: &Project, service: Option<&str>) -> Result<()> {
         bail!("Failed to start services:\n{}", stderr);
     }
 
+    if hooks && !project.hooks.post_start.is_empty() {
+        if !wait_until_ready(&project_path, service, READY_TIMEOUT) {
+            println!(
+                "\n  {} Services not ready after {}s, running hooks anyway",
+                "⚠".yellow(),
+                READY_TIMEOUT.as_secs()
+            );
+        }
+        run_hooks(project, "post_start", &project.hooks.post_start)?;
+    }
+
     Ok(())
 }
 
-/// Stop docker compose services
-pub fn stop_services(project: &Project, service: Option<&str>) -> Result<()> {
+/// Run the project's `pre_stop` hooks, then stop docker compose services
+pub fn stop_services(project: &Project, service: Option<&str>, hooks: bool) -> Result<()> {
     let project_path = project.expanded_path();
     let compose_file = match find_compose_file(&project_path) {
         Some(f) => f,
@@ -143,6 +393,13 @@ pub fn stop_services(project: &Project, service: Option<&str>) -> Result<()> {
         }
     };
 
+    if hooks && !project.hooks.pre_stop.is_empty() {
+        // A failing pre_stop hook must not keep services running
+        if let Err(e) = run_hooks(project, "pre_stop", &project.hooks.pre_stop) {
+            println!("  {} {}\n", "⚠".yellow(), e);
+        }
+    }
+
     println!("Stopping services for: {}\n", project.name.cyan().bold());
 
     let mut cmd = Command::new("docker");
@@ -166,6 +423,67 @@ pub fn stop_services(project: &Project, service: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// How long to wait for services to become ready before running hooks
+const READY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Poll compose status until the started service (or every service) is
+/// running and healthy, if it has a healthcheck. One-shot services that
+/// exited with code 0 count as ready. Returns false on timeout.
+fn wait_until_ready(project_path: &Path, service: Option<&str>, timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        let services: Vec<ServiceStatus> = compose_ps(project_path, true)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|s| service.is_none_or(|svc| s.name == svc))
+            .collect();
+        let ready = !services.is_empty()
+            && services.iter().all(|s| match s.state {
+                ServiceState::Running => s.health.as_deref().is_none_or(|h| h == "healthy"),
+                ServiceState::Exited => s.exit_code == Some(0),
+                _ => false,
+            });
+        if ready {
+            return true;
+        }
+        std::thread::sleep(Duration::from_secs(1));
+    }
+    false
+}
+
+/// Run lifecycle hooks in the project root with the project environment,
+/// stopping at the first failure.
+/// The output of a failing hook is shown in full.
+pub fn run_hooks(project: &Project, stage: &str, hooks: &[String]) -> Result<()> {
+    let hook_env = CommandEnv::build(project, None)?;
+    println!("\n  Hooks ({}):", stage);
+    for hook in hooks {
+        let started = Instant::now();
+        let output = hook_env
+            .shell(hook)
+            .output()
+            .with_context(|| format!("Failed to run hook: {}", hook))?;
+        let elapsed = started.elapsed().as_secs_f32();
+
+        if output.status.success() {
+            println!("   {} {} {}", "✓".green(), hook, format!("({:.1}s)", elapsed).dimmed());
+            continue;
+        }
+
+        println!("   {} {} {}", "✗".red(), hook, format!("({})", output.status).dimmed());
+        let combined = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        for line in combined.lines() {
+            println!("      {}", line.dimmed());
+        }
+        bail!("{} hook failed: {}", stage, hook);
+    }
+    Ok(())
+}
+
 /// Restart docker compose services
 pub fn restart_services(project: &Project, service: Option<&str>) -> Result<()> {
     let project_path = project.expanded_path();
@@ -193,19 +511,7 @@ pub fn restart_services(project: &Project, service: Option<&str>) -> Result<()>
         let services = get_compose_status(&project_path)?;
         if !services.is_empty() {
             println!("  Docker Compose:");
-            for (name, state, ports) in &services {
-                let icon = if state == "running" {
-                    "✓".green().to_string()
-                } else {
-                    "✗".red().to_string()
-                };
-                let port_info = if ports.is_empty() {
-                    String::new()
-                } else {
-                    format!(" ({})", ports)
-                };
-                println!("   {} {} {}{}", icon, name, state, port_info);
-            }
+            display::display_services(&services);
         }
         println!("\n{}", "Services restarted!".green().bold());
     } else {
@@ -258,3 +564,316 @@ pub fn show_logs(project: &Project, service: Option<&str>, follow: bool, lines:
 
     Ok(())
 }
+
+/// Stop and remove containers and networks, optionally including volumes
+pub fn down_services(project: &Project, volumes: bool) -> Result<()> {
+    let project_path = project.expanded_path();
+    let compose_file = match find_compose_file(&project_path) {
+        Some(f) => f,
+        None => {
+            println!("{}", "No docker-compose file found.".yellow());
+            return Ok(());
+        }
+    };
+
+    println!("Taking down services for: {}\n", project.name.cyan().bold());
+
+    let mut cmd = Command::new("docker");
+    cmd.args(["compose", "-f", &compose_file, "down"]);
+    cmd.current_dir(&project_path);
+
+    if volumes {
+        cmd.arg("--volumes");
+        println!("  {}", "Removing volumes as well".yellow());
+    }
+
+    let output = cmd.output().context("Failed to run docker compose down")?;
+
+    if output.status.success() {
+        println!("{}", "Services removed.".green().bold());
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to take down services:\n{}", stderr);
+    }
+
+    Ok(())
+}
+
+/// A named docker volume mounted by a compose service
+#[derive(Debug, Clone)]
+pub struct ServiceVolume {
+    /// Key under the compose file's top-level `volumes:`
+    pub key: String,
+    /// Actual docker volume name (usually prefixed with the compose project)
+    pub name: String,
+    pub target: String,
+}
+
+/// Resolve the named volumes a service mounts, using `docker compose config`
+pub fn service_volumes(project: &Project, service: &str) -> Result<Vec<ServiceVolume>> {
+    let project_path = project.expanded_path();
+    let compose_file = find_compose_file(&project_path)
+        .ok_or_else(|| anyhow::anyhow!("No docker-compose file found."))?;
+
+    let output = Command::new("docker")
+        .args(["compose", "-f", &compose_file, "config", "--format", "json"])
+        .current_dir(&project_path)
+        .output()
+        .context("Failed to run docker compose config")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to read compose configuration:\n{}", stderr);
+    }
+
+    let config: serde_json::Value =
+        serde_json::from_slice(&output.stdout).context("Failed to parse compose configuration")?;
+    let svc = &config["services"][service];
+    if svc.is_null() {
+        bail!("Service '{}' not found in compose file", service);
+    }
+
+    let mut volumes = Vec::new();
+    for mount in svc["volumes"].as_array().into_iter().flatten() {
+        if mount["type"] != "volume" {
+            continue;
+        }
+        let Some(key) = mount["source"].as_str() else {
+            continue;
+        };
+        let name = config["volumes"][key]["name"]
+            .as_str()
+            .unwrap_or(key)
+            .to_string();
+        volumes.push(ServiceVolume {
+            key: key.to_string(),
+            name,
+            target: mount["target"].as_str().unwrap_or("").to_string(),
+        });
+    }
+    Ok(volumes)
+}
+
+/// Run a docker compose subcommand for one service, failing on a non-zero exit
+fn compose_service_cmd(project: &Project, args: &[&str], service: &str) -> Result<()> {
+    let project_path = project.expanded_path();
+    let compose_file = find_compose_file(&project_path)
+        .ok_or_else(|| anyhow::anyhow!("No docker-compose file found."))?;
+
+    let output = Command::new("docker")
+        .args(["compose", "-f", &compose_file])
+        .args(args)
+        .arg(service)
+        .current_dir(&project_path)
+        .output()
+        .with_context(|| format!("Failed to run docker compose {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("docker compose {} {} failed:\n{}", args.join(" "), service, stderr);
+    }
+    Ok(())
+}
+
+/// Run a throwaway alpine container with the given volume mounts
+fn run_volume_container(mounts: &[String], script: &str) -> Result<()> {
+    let mut cmd = Command::new("docker");
+    cmd.args(["run", "--rm"]);
+    for mount in mounts {
+        cmd.args(["-v", mount]);
+    }
+    cmd.args(["alpine", "sh", "-c", script]);
+
+    let output = cmd.output().context("Failed to run docker")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Volume container failed:\n{}", stderr);
+    }
+    Ok(())
+}
+
+fn is_service_running(project: &Project, service: &str) -> bool {
+    get_compose_status(&project.expanded_path())
+        .map(|svcs| {
+            svcs.iter()
+                .any(|s| s.name == service && s.state.is_running())
+        })
+        .unwrap_or(false)
+}
+
+/// Recreate a service's container with its named volumes wiped
+pub fn reset_service(project: &Project, service: &str) -> Result<()> {
+    let volumes = service_volumes(project, service)?;
+
+    println!(
+        "Resetting {} for: {}\n",
+        service.cyan(),
+        project.name.cyan().bold()
+    );
+
+    compose_service_cmd(project, &["rm", "--stop", "--force"], service)?;
+    println!("  {} Removed container", "✓".green());
+
+    for volume in &volumes {
+        let output = Command::new("docker")
+            .args(["volume", "rm", "--force", &volume.name])
+            .output()
+            .context("Failed to run docker volume rm")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to remove volume {}:\n{}", volume.name, stderr);
+        }
+        println!("  {} Wiped volume {}", "✓".green(), volume.name);
+    }
+
+    compose_service_cmd(project, &["up", "-d"], service)?;
+    println!("  {} Recreated container", "✓".green());
+
+    println!("\n{}", "Service reset!".green().bold());
+    Ok(())
+}
+
+/// Snapshot every named volume of a service into
+/// `<backups_dir>/<service>/<timestamp>/<volume>.tar.gz`
+pub fn backup_service(project: &Project, service: &str, backups_dir: &Path) -> Result<PathBuf> {
+    let volumes = service_volumes(project, service)?;
+    if volumes.is_empty() {
+        bail!("Service '{}' has no named volumes to back up", service);
+    }
+
+    // Archives go to a hidden directory that is renamed once all succeeded,
+    // so a failed backup never shows up as the latest snapshot
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let dest = backups_dir.join(service).join(&timestamp);
+    let partial = backups_dir.join(service).join(format!(".{}.partial", timestamp));
+    std::fs::create_dir_all(&partial).context("Failed to create backup directory")?;
+
+    println!(
+        "Backing up {} for: {}\n",
+        service.cyan(),
+        project.name.cyan().bold()
+    );
+
+    // Stop the service so the snapshot is consistent
+    let was_running = is_service_running(project, service);
+    if was_running {
+        compose_service_cmd(project, &["stop"], service)?;
+    }
+
+    let result = volumes.iter().try_for_each(|volume| {
+        run_volume_container(
+            &[
+                format!("{}:/volume:ro", volume.name),
+                format!("{}:/backup", partial.display()),
+            ],
+            &format!("tar czf /backup/{}.tar.gz -C /volume .", volume.key),
+        )?;
+        println!("  {} {} → {}.tar.gz", "✓".green(), volume.name, volume.key);
+        Ok::<(), anyhow::Error>(())
+    });
+
+    // The backup is kept or discarded before a failed restart is reported
+    let restarted = if was_running {
+        compose_service_cmd(project, &["start"], service)
+    } else {
+        Ok(())
+    };
+    if let Err(e) = result {
+        std::fs::remove_dir_all(&partial).ok();
+        if let Err(restart) = restarted {
+            println!("  {} Failed to restart {}: {:#}", "⚠".yellow(), service, restart);
+        }
+        return Err(e);
+    }
+    std::fs::rename(&partial, &dest).context("Failed to finish backup directory")?;
+
+    println!("\n{} {}", "Backup saved to".green().bold(), dest.display());
+    restarted.with_context(|| format!("Backup is complete, but {} failed to restart", service))?;
+    Ok(dest)
+}
+
+/// Backup snapshots of a service, oldest first. Unfinished backups are
+/// not listed.
+pub fn list_backups(service: &str, backups_dir: &Path) -> Vec<PathBuf> {
+    let mut snapshots: Vec<PathBuf> = std::fs::read_dir(backups_dir.join(service))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    snapshots.sort();
+    snapshots
+}
+
+/// Restore a service's named volumes from a backup snapshot directory.
+/// Fails if the snapshot has no archive for any of them.
+pub fn restore_service(project: &Project, service: &str, snapshot: &Path) -> Result<()> {
+    let volumes = service_volumes(project, service)?;
+    let (volumes, skipped): (Vec<_>, Vec<_>) = volumes
+        .into_iter()
+        .partition(|v| snapshot.join(format!("{}.tar.gz", v.key)).exists());
+    if volumes.is_empty() {
+        bail!(
+            "Snapshot {} has no archive for any volume of '{}'",
+            snapshot.display(),
+            service
+        );
+    }
+
+    println!(
+        "Restoring {} for: {} from {}\n",
+        service.cyan(),
+        project.name.cyan().bold(),
+        snapshot.display()
+    );
+
+    let was_running = is_service_running(project, service);
+    if was_running {
+        compose_service_cmd(project, &["stop"], service)?;
+    }
+
+    let result = volumes.iter().try_for_each(|volume| {
+        let archive = format!("{}.tar.gz", volume.key);
+        run_volume_container(
+            &[
+                format!("{}:/volume", volume.name),
+                format!("{}:/backup:ro", snapshot.display()),
+            ],
+            &format!(
+                "find /volume -mindepth 1 -delete && tar xzf /backup/{} -C /volume",
+                archive
+            ),
+        )?;
+        println!("  {} {} ← {}", "✓".green(), volume.name, archive);
+        Ok::<(), anyhow::Error>(())
+    });
+
+    if was_running {
+        compose_service_cmd(project, &["start"], service)?;
+    }
+    result?;
+
+    if !skipped.is_empty() {
+        for volume in &skipped {
+            println!(
+                "  {} {} not restored: no {}.tar.gz in snapshot",
+                "⚠".yellow(),
+                volume.name,
+                volume.key
+            );
+        }
+        let summary = format!(
+            "Service partially restored ({} of {} volumes)",
+            volumes.len(),
+            volumes.len() + skipped.len()
+        );
+        println!("\n{}", summary.yellow().bold());
+        return Ok(());
+    }
+    println!("\n{}", "Service restored!".green().bold());
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use std::path::Path;
+
+use crate::project::parse_yaml_section_keys;
+
+/// A command found in one of the project's own task runners
+#[derive(Debug, Clone)]
+pub struct DetectedTask {
+    pub name: String,
+    pub command: String,
+    /// File the task was read from (e.g. `package.json`, `Makefile`)
+    pub source: String,
+}
+
+impl DetectedTask {
+    fn new(name: &str, command: String, source: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            command,
+            source: source.to_string(),
+        }
+    }
+}
+
+/// Discover the commands defined by the project's task runners: package.json
+/// scripts, Makefile targets, justfile recipes, Taskfile tasks, poetry/pdm
+/// scripts and Cargo aliases
+pub fn discover(path: &Path) -> Vec<DetectedTask> {
+    let mut tasks = Vec::new();
+    tasks.extend(package_json_scripts(path));
+    tasks.extend(makefile_targets(path));
+    tasks.extend(justfile_recipes(path));
+    tasks.extend(taskfile_tasks(path));
+    tasks.extend(pyproject_scripts(path));
+    tasks.extend(cargo_aliases(path));
+    tasks
+}
+
+fn read(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+/// The `<pm> run` prefix matching the project's lockfile
+fn node_runner(path: &Path) -> &'static str {
+    if path.join("pnpm-lock.yaml").exists() {
+        "pnpm run"
+    } else if path.join("yarn.lock").exists() {
+        "yarn run"
+    } else if path.join("bun.lockb").exists() || path.join("bun.lock").exists() {
+        "bun run"
+    } else {
+        "npm run"
+    }
+}
+
+fn package_json_scripts(path: &Path) -> Vec<DetectedTask> {
+    let Some(content) = read(&path.join("package.json")) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    let Some(scripts) = json["scripts"].as_object() else {
+        return Vec::new();
+    };
+
+    let runner = node_runner(path);
+    scripts
+        .keys()
+        // Lifecycle scripts (prebuild, postinstall, ...) run implicitly
+        .filter(|name| {
+            let base = name
+                .strip_prefix("pre")
+                .or_else(|| name.strip_prefix("post"));
+            !base.is_some_and(|b| scripts.contains_key(b) || b == "install")
+        })
+        .map(|name| DetectedTask::new(name, format!("{} {}", runner, name), "package.json"))
+        .collect()
+}
+
+fn makefile_targets(path: &Path) -> Vec<DetectedTask> {
+    let Some((file, content)) = ["Makefile", "makefile", "GNUmakefile"]
+        .iter()
+        .find_map(|f| read(&path.join(f)).map(|c| (*f, c)))
+    else {
+        return Vec::new();
+    };
+
+    let mut tasks: Vec<DetectedTask> = Vec::new();
+    for line in content.lines() {
+        if line.starts_with(['\t', ' ', '.', '#']) {
+            continue;
+        }
+        let Some((targets, rest)) = line.split_once(':') else {
+            continue;
+        };
+        // Skip variable assignments (`X := y`, `X ::= y`) and pattern rules
+        if rest.starts_with('=') || rest.starts_with(":=") || targets.contains(['=', '%', '$']) {
+            continue;
+        }
+        for target in targets.split_whitespace() {
+            if !tasks.iter().any(|t| t.name == target) {
+                tasks.push(DetectedTask::new(target, format!("make {}", target), file));
+            }
+        }
+    }
+    tasks
+}
+
+fn justfile_recipes(path: &Path) -> Vec<DetectedTask> {
+    let Some((file, content)) = ["justfile", "Justfile", ".justfile"]
+        .iter()
+        .find_map(|f| read(&path.join(f)).map(|c| (*f, c)))
+    else {
+        return Vec::new();
+    };
+
+    const KEYWORDS: &[&str] = &["set", "alias", "export", "import", "mod"];
+    let mut tasks = Vec::new();
+    for line in content.lines() {
+        if line.starts_with([' ', '\t', '#', '[']) {
+            continue;
+        }
+        let Some((head, rest)) = line.split_once(':') else {
+            continue;
+        };
+        if rest.starts_with('=') {
+            continue;
+        }
+        let mut words = head.split_whitespace();
+        let Some(name) = words.next().map(|n| n.trim_start_matches('@')) else {
+            continue;
+        };
+        if KEYWORDS.contains(&name) || name.starts_with('_') || name.is_empty() {
+            continue;
+        }
+        tasks.push(DetectedTask::new(name, format!("just {}", name), file));
+    }
+    tasks
+}
+
+fn taskfile_tasks(path: &Path) -> Vec<DetectedTask> {
+    let Some((file, content)) = ["Taskfile.yml", "Taskfile.yaml", "taskfile.yml"]
+        .iter()
+        .find_map(|f| read(&path.join(f)).map(|c| (*f, c)))
+    else {
+        return Vec::new();
+    };
+
+    parse_yaml_section_keys(&content, "tasks")
+        .iter()
+        .map(|name| DetectedTask::new(name, format!("task {}", name), file))
+        .collect()
+}
+
+fn pyproject_scripts(path: &Path) -> Vec<DetectedTask> {
+    let Some(content) = read(&path.join("pyproject.toml")) else {
+        return Vec::new();
+    };
+    let Ok(doc) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let tool = doc.get("tool");
+
+    let mut tasks = Vec::new();
+    for (runner, key) in [("poetry", "poetry.scripts"), ("pdm", "pdm.scripts")] {
+        let (section, field) = key.split_once('.').unwrap_or((key, ""));
+        let Some(scripts) = tool
+            .and_then(|t| t.get(section))
+            .and_then(|t| t.get(field))
+            .and_then(|t| t.as_table())
+        else {
+            continue;
+        };
+        for name in scripts.keys() {
+            // pdm keeps its settings under the same table
+            if runner == "pdm" && name.starts_with('_') {
+                continue;
+            }
+            tasks.push(DetectedTask::new(
+                name,
+                format!("{} run {}", runner, name),
+                "pyproject.toml",
+            ));
+        }
+    }
+    tasks
+}
+
+fn cargo_aliases(path: &Path) -> Vec<DetectedTask> {
+    let Some((file, content)) = [".cargo/config.toml", ".cargo/config"]
+        .iter()
+        .find_map(|f| read(&path.join(f)).map(|c| (*f, c)))
+    else {
+        return Vec::new();
+    };
+    let Ok(doc) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let Some(aliases) = doc.get("alias").and_then(|a| a.as_table()) else {
+        return Vec::new();
+    };
+
+    aliases
+        .keys()
+        .map(|name| DetectedTask::new(name, format!("cargo {}", name), file))
+        .collect()
+}
@@ -0,0 +1,428 @@
+use anyhow::{bail, Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::Duration;
+
+use crate::activity::{self, Activity, Frecency};
+use crate::cache::StatusCache;
+use crate::config::ConfigManager;
+use crate::display::time_ago;
+use crate::git::GitInfo;
+use crate::probe::ProbeCache;
+use crate::project::Project;
+use crate::services::{ServiceState, ServiceStatus};
+
+/// How often service and git state is probed again in the background
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// What the user picked in the dashboard. Everything but `Quit` runs with
+/// the terminal restored, after which the dashboard is opened again.
+pub enum Action {
+    Quit,
+    Switch(String),
+    Start(String),
+    Stop(String),
+    Logs(String),
+    Run(String, String),
+}
+
+/// Service and git state of one project
+#[derive(Clone, Default)]
+struct Status {
+    services: Vec<ServiceStatus>,
+    git: Option<GitInfo>,
+}
+
+struct App {
+    projects: Vec<Project>,
+    status: Vec<Status>,
+    frecency: HashMap<String, Frecency>,
+    events: Vec<Activity>,
+    table: TableState,
+    /// Commands of the selected project, while the run picker is open
+    picker: Option<(Vec<String>, ListState)>,
+    refreshed: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Run the dashboard over `projects` (with their discovered tasks) until
+/// the user picks an action. `selected` restores the previous selection.
+pub fn run(config: &ConfigManager, projects: Vec<Project>, selected: Option<&str>) -> Result<Action> {
+    if !std::io::stdout().is_terminal() {
+        bail!("The dashboard needs a terminal");
+    }
+    let events = activity::load(config)?;
+    let ranking = activity::frecency(&projects, &events);
+    let mut projects = projects;
+    projects.sort_by_key(|p| ranking.iter().position(|f| f.project == p.name));
+
+    // Start from the last-known state while the first probe runs
+    let cache = StatusCache::load(config)?;
+    let status = projects
+        .iter()
+        .map(|p| match cache.entry(&p.name) {
+            Some(entry) => Status {
+                services: entry.services.clone(),
+                git: entry.git.clone(),
+            },
+            None => Status::default(),
+        })
+        .collect();
+
+    let mut table = TableState::default();
+    let index = selected.and_then(|name| projects.iter().position(|p| p.name == name));
+    table.select(Some(index.unwrap_or(0)).filter(|_| !projects.is_empty()));
+
+    let (refresh_tx, updates) = spawn_refresher(projects.clone());
+    let mut app = App {
+        projects,
+        status,
+        frecency: ranking.into_iter().map(|f| (f.project.clone(), f)).collect(),
+        events,
+        table,
+        picker: None,
+        refreshed: None,
+    };
+
+    // Each init wraps the current panic hook; put the previous one back on
+    // close so reopening the dashboard does not stack hooks
+    let previous_hook = std::panic::take_hook();
+    let mut terminal = match ratatui::try_init() {
+        Ok(terminal) => terminal,
+        Err(e) => {
+            ratatui::restore();
+            std::panic::set_hook(previous_hook);
+            return Err(e).context("Failed to initialize the terminal");
+        }
+    };
+    let action = app.event_loop(&mut terminal, &updates, &refresh_tx);
+    ratatui::restore();
+    std::panic::set_hook(previous_hook);
+    action
+}
+
+/// Probe all projects now and every `REFRESH_INTERVAL`, or sooner when
+/// asked through the returned sender. Stops once the dashboard closes.
+fn spawn_refresher(projects: Vec<Project>) -> (Sender<()>, Receiver<Vec<Status>>) {
+    let (refresh_tx, refresh_rx) = mpsc::channel::<()>();
+    let (update_tx, update_rx) = mpsc::channel();
+    std::thread::spawn(move || loop {
+        let probes = ProbeCache::new();
+        probes.prefetch_compose(&projects);
+        probes.prefetch_git(&projects);
+        let status: Vec<Status> = projects
+            .iter()
+            .map(|p| Status {
+                services: probes.compose_status(p),
+                git: probes.git_info(p),
+            })
+            .collect();
+        if update_tx.send(status).is_err() {
+            break;
+        }
+        match refresh_rx.recv_timeout(REFRESH_INTERVAL) {
+            Ok(()) | Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    });
+    (refresh_tx, update_rx)
+}
+
+impl App {
+    fn event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        updates: &Receiver<Vec<Status>>,
+        refresh: &Sender<()>,
+    ) -> Result<Action> {
+        loop {
+            if let Some(status) = updates.try_iter().last() {
+                self.status = status;
+                self.refreshed = Some(chrono::Utc::now());
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(action) = self.handle_key(key.code, refresh) {
+                return Ok(action);
+            }
+        }
+    }
+
+    fn selected(&self) -> Option<&Project> {
+        self.table.selected().and_then(|i| self.projects.get(i))
+    }
+
+    fn handle_key(&mut self, key: KeyCode, refresh: &Sender<()>) -> Option<Action> {
+        if let Some((commands, state)) = &mut self.picker {
+            match key {
+                KeyCode::Esc | KeyCode::Char('q') => self.picker = None,
+                KeyCode::Down | KeyCode::Char('j') => state.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => state.select_previous(),
+                KeyCode::Enter => {
+                    let command = state.selected().and_then(|i| commands.get(i)).cloned();
+                    let project = self.selected().map(|p| p.name.clone());
+                    if let (Some(project), Some(command)) = (project, command) {
+                        return Some(Action::Run(project, command));
+                    }
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        let name = self.selected().map(|p| p.name.clone());
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Some(Action::Quit),
+            KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+            KeyCode::Home | KeyCode::Char('g') => self.table.select_first(),
+            KeyCode::End | KeyCode::Char('G') => self.table.select_last(),
+            KeyCode::Char('R') => {
+                refresh.send(()).ok();
+            }
+            KeyCode::Enter => return name.map(Action::Switch),
+            KeyCode::Char('s') => return name.map(Action::Start),
+            KeyCode::Char('x') => return name.map(Action::Stop),
+            KeyCode::Char('l') => return name.map(Action::Logs),
+            KeyCode::Char('r') => {
+                if let Some(project) = self.selected() {
+                    let mut commands: Vec<String> = project.commands.keys().cloned().collect();
+                    commands.sort();
+                    if !commands.is_empty() {
+                        self.picker = Some((commands, ListState::default().with_selected(Some(0))));
+                    }
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [list, details] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(main);
+
+        self.draw_projects(frame, list);
+        self.draw_details(frame, details);
+
+        let refreshed = self
+            .refreshed
+            .map_or_else(|| "probing...".to_string(), |t| format!("updated {}", time_ago(t)));
+        let keys = if self.picker.is_some() {
+            " ↑↓ select  enter run  esc cancel"
+        } else {
+            " ↑↓ move  enter switch  s start  x stop  l logs  r run  R refresh  q quit"
+        };
+        let help_line = Line::from(vec![
+            Span::raw(keys).dark_gray(),
+            Span::raw(format!("  ·  {}", refreshed)).dark_gray(),
+        ]);
+        frame.render_widget(Paragraph::new(help_line), help);
+
+        if let Some((commands, state)) = &mut self.picker {
+            let height = (commands.len() as u16 + 2).min(frame.area().height.saturating_sub(4));
+            let area = centered(frame.area(), 40, height);
+            let items: Vec<ListItem> = commands.iter().map(|c| ListItem::new(c.as_str())).collect();
+            let list = List::new(items)
+                .block(Block::bordered().title(" Run command "))
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+                .highlight_symbol("› ");
+            frame.render_widget(Clear, area);
+            frame.render_stateful_widget(list, area, state);
+        }
+    }
+
+    fn draw_projects(&mut self, frame: &mut Frame, area: Rect) {
+        let rows: Vec<Row> = self
+            .projects
+            .iter()
+            .zip(&self.status)
+            .map(|(project, status)| {
+                let missing = !project.exists();
+                Row::new(vec![
+                    Cell::from(project.name.clone()).style(if missing {
+                        Style::new().fg(Color::DarkGray)
+                    } else {
+                        Style::new().fg(Color::Cyan)
+                    }),
+                    Cell::from(project.project_type.clone()),
+                    services_cell(&status.services),
+                    git_cell(status.git.as_ref()),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(35),
+                Constraint::Percentage(20),
+                Constraint::Length(10),
+                Constraint::Min(10),
+            ],
+        )
+        .header(Row::new(vec!["Project", "Type", "Services", "Git"]).bold())
+        .block(Block::bordered().title(format!(" Projects ({}) ", self.projects.len())))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("› ");
+        frame.render_stateful_widget(table, area, &mut self.table);
+    }
+
+    fn draw_details(&self, frame: &mut Frame, area: Rect) {
+        let Some(index) = self.table.selected() else {
+            let empty = Paragraph::new("No projects registered. Add one with 'projectctl add'.")
+                .block(Block::bordered());
+            frame.render_widget(empty, area);
+            return;
+        };
+        let project = &self.projects[index];
+        let status = &self.status[index];
+
+        let mut lines = vec![
+            Line::from(project.path.clone()).dark_gray(),
+            Line::from(format!("Type: {}", project.type_label())),
+            Line::default(),
+            Line::from("Services").bold(),
+        ];
+        if status.services.is_empty() {
+            lines.push(Line::from("  none running").dark_gray());
+        }
+        for svc in &status.services {
+            let color = match svc.state {
+                ServiceState::Running => Color::Green,
+                ServiceState::Restarting | ServiceState::Paused | ServiceState::Created => Color::Yellow,
+                _ => Color::Red,
+            };
+            let ports: Vec<String> = svc.ports.iter().map(|p| p.to_string()).collect();
+            lines.push(Line::from(vec![
+                Span::raw("  ● ").fg(color),
+                Span::raw(format!("{:<14} {}", svc.name, svc.state.as_str())),
+                Span::raw(format!(" {}", ports.join(", "))).dark_gray(),
+            ]));
+        }
+
+        lines.push(Line::default());
+        lines.push(Line::from("Git").bold());
+        match &status.git {
+            Some(git) => {
+                lines.push(Line::from(format!("  {} · {}", git.branch, git.sync_string())));
+                lines.push(Line::from(format!("  {}", git.status_string())));
+                if let Some(ref state) = git.state {
+                    lines.push(Line::from(format!("  {} in progress", state)).red());
+                }
+                if git.stash_count > 0 {
+                    lines.push(Line::from(format!("  {} stashed", git.stash_count)));
+                }
+                lines.push(
+                    Line::from(format!("  {} ({})", git.last_commit_message, git.last_commit_time))
+                        .dark_gray(),
+                );
+            }
+            None => lines.push(Line::from("  not a repository").dark_gray()),
+        }
+
+        lines.push(Line::default());
+        let mut commands: Vec<&String> = project.commands.keys().collect();
+        commands.sort();
+        lines.push(Line::from("Commands").bold());
+        lines.push(if commands.is_empty() {
+            Line::from("  none").dark_gray()
+        } else {
+            Line::from(format!(
+                "  {}",
+                commands.iter().map(|c| c.as_str()).collect::<Vec<_>>().join(", ")
+            ))
+        });
+
+        lines.push(Line::default());
+        lines.push(Line::from("Recent activity").bold());
+        let recent: Vec<&Activity> = self
+            .events
+            .iter()
+            .rev()
+            .filter(|e| e.project.split([':', '@']).next() == Some(project.name.as_str()))
+            .take(5)
+            .collect();
+        if recent.is_empty() {
+            lines.push(Line::from("  none recorded").dark_gray());
+        }
+        for event in recent {
+            lines.push(Line::from(format!(
+                "  {:<8} {:<20} {}",
+                format!("{:?}", event.kind).to_lowercase(),
+                event.project,
+                time_ago(event.time)
+            )));
+        }
+        if let Some(f) = self.frecency.get(&project.name) {
+            lines.push(Line::from(format!("  frecency {:.2}: {}", f.score, f.breakdown())).dark_gray());
+        }
+
+        let details = Paragraph::new(lines)
+            .block(Block::bordered().title(format!(" {} ", project.name)))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(details, area);
+    }
+}
+
+/// `● 2/3` when services are running, `○ 0/3` when all are stopped
+fn services_cell(services: &[ServiceStatus]) -> Cell<'static> {
+    if services.is_empty() {
+        return Cell::from("-").dark_gray();
+    }
+    let running = services.iter().filter(|s| s.state.is_running()).count();
+    if running > 0 {
+        Cell::from(format!("● {}/{}", running, services.len())).green()
+    } else {
+        Cell::from(format!("○ 0/{}", services.len())).dark_gray()
+    }
+}
+
+/// Branch with dirty count and ahead/behind, e.g. `main ✎3 ↑1`
+fn git_cell(git: Option<&GitInfo>) -> Cell<'static> {
+    let Some(git) = git else {
+        return Cell::from("-").dark_gray();
+    };
+    let mut spans = vec![Span::raw(git.branch.clone())];
+    if git.dirty_count() > 0 {
+        spans.push(Span::raw(format!(" ✎{}", git.dirty_count())).yellow());
+    }
+    if git.ahead > 0 {
+        spans.push(Span::raw(format!(" ↑{}", git.ahead)).cyan());
+    }
+    if git.behind > 0 {
+        spans.push(Span::raw(format!(" ↓{}", git.behind)).magenta());
+    }
+    if git.state.is_some() || !git.conflicted.is_empty() {
+        spans.push(Span::raw(" !").red());
+    }
+    Cell::from(Line::from(spans))
+}
+
+/// A `width` x `height` area in the middle of `area`
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::io::{IsTerminal, Write};
+
+use crate::env::CommandEnv;
+use crate::project::{self, Project, ProjectCommand};
+
+/// Walk through what was detected for a new project and let the user
+/// accept, edit or remove the name, type, services and each command.
+/// Commands whose programs are not on PATH are flagged, and listed again
+/// before saving, which then defaults to no.
+/// Returns whether the project should be saved.
+pub fn review(project: &mut Project, registered: &[Project]) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        bail!("--interactive needs a terminal");
+    }
+    println!("{} {}", "Reviewing".bold(), project.path.dimmed());
+    println!("{}\n", "Press enter to keep the value in brackets.".dimmed());
+
+    loop {
+        let name = ask("Name", &project.name);
+        if name.contains([':', '@']) {
+            println!("  {} Names cannot contain ':' or '@'", "✗".red());
+        } else if registered.iter().any(|p| p.name == name) {
+            println!("  {} '{}' is already registered", "✗".red(), name);
+        } else {
+            project.name = name;
+            break;
+        }
+    }
+
+    if project.stacks.len() > 1 {
+        println!("  Detected stacks: {}", project.stacks.join(", "));
+    }
+    let project_type = ask("Type", &project.project_type);
+    if project_type != project.project_type {
+        // Another type brings its own commands
+        let path = project.expanded_path();
+        let stacks = Project::detect_stacks(&path, Some(&project_type));
+        project.project_type = project_type;
+        project.stacks = project::stack_types(&stacks);
+        project.commands = Project::detect_stack_commands(&path, &stacks)
+            .into_iter()
+            .map(|(name, cmd)| (name, cmd.into()))
+            .collect();
+        project.packages = Project::initial_packages(&path, &project.project_type);
+    }
+
+    if !project.services.is_empty() {
+        println!("\n{}", "Services".bold());
+        let services = std::mem::take(&mut project.services);
+        project.services = services
+            .into_iter()
+            .filter(|svc| ask_yes(&format!("  Keep {}?", svc.cyan()), true))
+            .collect();
+    }
+
+    println!("\n{}", "Commands".bold());
+    let mut names: Vec<String> = project.commands.keys().cloned().collect();
+    names.sort();
+    for name in names {
+        let command = project.commands.remove(&name).unwrap();
+        if let Some(command) = review_command(project, &name, command) {
+            project.commands.insert(name, command);
+        }
+    }
+    loop {
+        let name = ask("  Add a command (name, empty to finish)", "");
+        if name.is_empty() {
+            break;
+        }
+        let script = ask("    Command", "");
+        if script.is_empty() {
+            continue;
+        }
+        if let Some(command) = review_command(project, &name, script.into()) {
+            project.commands.insert(name, command);
+        }
+    }
+
+    // Commands kept despite the warnings are listed once more
+    let mut broken: Vec<(&String, Vec<String>)> = project
+        .commands
+        .iter()
+        .map(|(name, command)| (name, missing_programs(project, command)))
+        .filter(|(_, missing)| !missing.is_empty())
+        .collect();
+    broken.sort();
+    println!();
+    if !broken.is_empty() {
+        println!("{} Commands with programs not found on PATH:", "⚠".yellow());
+        for (name, missing) in &broken {
+            println!("  {} needs {}", name.cyan(), missing.join(", "));
+        }
+        println!();
+    }
+    Ok(ask_yes("Save project?", broken.is_empty()))
+}
+
+/// Show one command and ask to keep, edit or remove it. A command whose
+/// programs are missing defaults to editing. Returns None when removed.
+fn review_command(project: &Project, name: &str, mut command: ProjectCommand) -> Option<ProjectCommand> {
+    loop {
+        println!("  {} = {}", name.cyan(), command);
+        let missing = missing_programs(project, &command);
+        if !missing.is_empty() {
+            println!("    {} Not found on PATH: {}", "⚠".yellow(), missing.join(", "));
+        }
+        let default = if missing.is_empty() { "k" } else { "e" };
+        match ask("    [k]eep, [e]dit or [r]emove", default).to_lowercase().as_str() {
+            "k" | "keep" => return Some(command),
+            "r" | "remove" => return None,
+            "e" | "edit" => {
+                let script = ask("    Command", &command.to_string());
+                command = script.into();
+            }
+            _ => println!("    Answer k, e or r"),
+        }
+    }
+}
+
+/// Programs of a command that are not on the PATH it would run with
+/// (including the project's venv and node_modules/.bin)
+fn missing_programs(project: &Project, command: &ProjectCommand) -> Vec<String> {
+    let def = command.def();
+    let Ok(env) = CommandEnv::build(project, Some(&def)) else {
+        return Vec::new();
+    };
+    def.scripts()
+        .iter()
+        .flat_map(|script| env.missing_programs(script))
+        .collect()
+}
+
+/// Ask for a value; an empty answer keeps `default`. End of input
+/// (Ctrl-D) aborts without saving.
+fn ask(question: &str, default: &str) -> String {
+    if default.is_empty() {
+        print!("{}: ", question);
+    } else {
+        print!("{} [{}]: ", question, default);
+    }
+    std::io::stdout().flush().ok();
+    let mut answer = String::new();
+    if matches!(std::io::stdin().read_line(&mut answer), Ok(0) | Err(_)) {
+        println!("\nAborted.");
+        std::process::exit(1);
+    }
+    match answer.trim() {
+        "" => default.to_string(),
+        answer => answer.to_string(),
+    }
+}
+
+/// Ask a yes/no question with a default
+fn ask_yes(question: &str, default: bool) -> bool {
+    let hint = if default { "Y/n" } else { "y/N" };
+    match ask(&format!("{} [{}]", question, hint), "").to_lowercase().as_str() {
+        "" => default,
+        answer => matches!(answer, "y" | "yes"),
+    }
+}
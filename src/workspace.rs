@@ -0,0 +1,200 @@
+use std::path::{Path, PathBuf};
+
+/// A member of a workspace declared in one of the root's manifests
+#[derive(Debug, Clone)]
+pub struct WorkspaceMember {
+    /// Directory name, used to address the member as `project:name`
+    pub name: String,
+    /// Path relative to the workspace root
+    pub path: String,
+}
+
+/// Discover workspace members from pnpm/npm/yarn workspaces, Cargo
+/// workspaces and go.work
+pub fn discover(root: &Path) -> Vec<WorkspaceMember> {
+    let mut members: Vec<WorkspaceMember> = Vec::new();
+    let sources = [
+        pnpm_patterns(root),
+        package_json_patterns(root),
+        cargo_patterns(root),
+        go_work_dirs(root),
+    ];
+    for (include, exclude) in sources {
+        for dir in include.iter().flat_map(|p| expand(root, p)) {
+            let Ok(rel) = dir.strip_prefix(root) else {
+                continue;
+            };
+            let rel = rel.to_string_lossy().to_string();
+            if rel.is_empty()
+                || exclude.iter().any(|p| expand(root, p).contains(&dir))
+                || members.iter().any(|m| m.path == rel)
+            {
+                continue;
+            }
+            let mut name = dir
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            // `apps/web` and `packages/web` become `web` and `packages-web`
+            if members.iter().any(|m| m.name == name) {
+                name = rel.replace('/', "-");
+            }
+            members.push(WorkspaceMember { name, path: rel });
+        }
+    }
+    members
+}
+
+/// Include and exclude patterns of a workspace manifest
+type Patterns = (Vec<String>, Vec<String>);
+
+fn read(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+/// Split patterns into includes and `!`-prefixed excludes
+fn split_excludes(patterns: Vec<String>) -> Patterns {
+    let (exclude, include): (Vec<String>, Vec<String>) =
+        patterns.into_iter().partition(|p| p.starts_with('!'));
+    let exclude = exclude
+        .into_iter()
+        .map(|p| p.trim_start_matches('!').to_string())
+        .collect();
+    (include, exclude)
+}
+
+/// `packages:` list of pnpm-workspace.yaml
+fn pnpm_patterns(root: &Path) -> Patterns {
+    let Some(content) = read(&root.join("pnpm-workspace.yaml")) else {
+        return Patterns::default();
+    };
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(' ') && !line.starts_with('-') {
+            in_packages = trimmed.starts_with("packages:");
+            continue;
+        }
+        if in_packages {
+            if let Some(item) = trimmed.strip_prefix('-') {
+                let item = item.split(" #").next().unwrap_or_default();
+                patterns.push(item.trim().trim_matches(|c| c == '"' || c == '\'').to_string());
+            }
+        }
+    }
+    split_excludes(patterns)
+}
+
+/// `workspaces` of package.json, as an array or `{ "packages": [...] }` (yarn)
+fn package_json_patterns(root: &Path) -> Patterns {
+    let Some(content) = read(&root.join("package.json")) else {
+        return Patterns::default();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Patterns::default();
+    };
+    let workspaces = &json["workspaces"];
+    let list = workspaces
+        .as_array()
+        .or_else(|| workspaces["packages"].as_array());
+    let patterns = list
+        .map(|l| {
+            l.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    split_excludes(patterns)
+}
+
+/// `[workspace] members` and `exclude` of Cargo.toml
+fn cargo_patterns(root: &Path) -> Patterns {
+    let Some(content) = read(&root.join("Cargo.toml")) else {
+        return Patterns::default();
+    };
+    let Ok(manifest) = content.parse::<toml::Table>() else {
+        return Patterns::default();
+    };
+    let Some(workspace) = manifest.get("workspace").and_then(|w| w.as_table()) else {
+        return Patterns::default();
+    };
+    let strings = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    (strings("members"), strings("exclude"))
+}
+
+/// `use` directives of go.work, single-line or in a block
+fn go_work_dirs(root: &Path) -> Patterns {
+    let Some(content) = read(&root.join("go.work")) else {
+        return Patterns::default();
+    };
+    let mut dirs = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() {
+                dirs.push(line.to_string());
+            }
+        } else if let Some(rest) = line.strip_prefix("use") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+            } else if !rest.is_empty() {
+                dirs.push(rest.to_string());
+            }
+        }
+    }
+    (dirs, Vec::new())
+}
+
+/// Directories matching a workspace pattern. A `*` may appear in the last
+/// path component (`packages/*`, `crates/app-*`); `**` matches one level.
+fn expand(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    let pattern = pattern.strip_suffix("/**").map_or(pattern.to_string(), |p| format!("{}/*", p));
+    let (parent, last) = match pattern.rsplit_once('/') {
+        Some((parent, last)) => (root.join(parent), last.to_string()),
+        None => (root.to_path_buf(), pattern.clone()),
+    };
+
+    if !last.contains('*') {
+        let dir = parent.join(&last);
+        return if dir.is_dir() { vec![dir] } else { Vec::new() };
+    }
+
+    let Ok(entries) = std::fs::read_dir(&parent) else {
+        return Vec::new();
+    };
+    let (prefix, suffix) = last.split_once('*').unwrap_or_default();
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter(|p| {
+            let name = p.file_name().unwrap_or_default().to_string_lossy();
+            !name.starts_with('.')
+                && name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}